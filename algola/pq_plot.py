@@ -69,3 +69,22 @@ with plt.style.context(["science", "nature"]):
         ax.plot(t_hats[i-1], dccs[i-1], styles[i-1], label=rf'$\hat{{q}}_\text{{dcc}} ~(N=2^{i} - 1)$', alpha=0.7)
     ax.legend()
     fig.savefig('harmonic_dcc.png', dpi=600, bbox_inches='tight')
+
+de = pd.read_parquet('errors.parquet')
+ns = np.array(de['n'][:])
+
+with plt.style.context(["science", "nature"]):
+    fig, ax = plt.subplots()
+    ax.set(xlabel=r'$N$', ylabel=r'$\|\hat{q} - q_\text{true}\|_2$', xscale='log', yscale='log')
+    ax.plot(ns, de['dc_l2'], 'o-', label=r'dc')
+    ax.plot(ns, de['dcc_l2'], 's--', label=r'dcc')
+    ax.legend()
+    fig.savefig('harmonic_error.png', dpi=600, bbox_inches='tight')
+
+with plt.style.context(["science", "nature"]):
+    fig, ax = plt.subplots()
+    ax.set(xlabel=r'$N$', ylabel=r'$S[\hat{q}] - S_\text{opt}$', xscale='log')
+    ax.plot(ns, de['dc_gap'], 'o-', label=r'dc')
+    ax.plot(ns, de['dcc_gap'], 's--', label=r'dcc')
+    ax.legend()
+    fig.savefig('harmonic_gap.png', dpi=600, bbox_inches='tight')
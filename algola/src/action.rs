@@ -0,0 +1,281 @@
+use itertools::Itertools;
+use peroxide::fuga::*;
+use reinla::discrete::DiscreteLagrangian;
+use reinla::lagrangian::Lagrangian;
+
+/// Criterion used to pick the best node combination, shared with the reinla lattices
+pub use reinla::lattice::SearchMode;
+
+/// Path from `start` at t0 to `end` at t0 + T under `lagrangian`
+#[allow(non_snake_case)]
+pub struct Problem<'a, L: Lagrangian> {
+    pub T: f64,
+    /// Start time; only matters for explicitly time-dependent Lagrangians
+    pub t0: f64,
+    pub start: L::Q,
+    pub end: L::Q,
+    pub lagrangian: &'a L,
+}
+
+impl<L: Lagrangian> Clone for Problem<'_, L> {
+    fn clone(&self) -> Self {
+        Self {
+            T: self.T,
+            t0: self.t0,
+            start: self.start.clone(),
+            end: self.end.clone(),
+            lagrangian: self.lagrangian,
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+impl<'a, L: Lagrangian> Problem<'a, L> {
+    pub fn new(T: f64, start: L::Q, end: L::Q, lagrangian: &'a L) -> Self {
+        Self {
+            T,
+            t0: 0f64,
+            start,
+            end,
+            lagrangian,
+        }
+    }
+
+    /// Two halves of duration T / 2 meeting at `mid`
+    pub fn split(&self, mid: L::Q) -> (Self, Self) {
+        let T = self.T / 2f64;
+        (
+            Self {
+                T,
+                t0: self.t0,
+                start: self.start.clone(),
+                end: mid.clone(),
+                lagrangian: self.lagrangian,
+            },
+            Self {
+                T,
+                t0: self.t0 + T,
+                start: mid,
+                end: self.end.clone(),
+                lagrangian: self.lagrangian,
+            },
+        )
+    }
+
+    /// Time step when `n` interior nodes split [t0, t0 + T] evenly
+    pub fn dt(&self, n: usize) -> f64 {
+        self.T / ((n + 1) as f64)
+    }
+
+    /// Interior nodes with both ends attached
+    fn path(&self, nodes: &[L::Q]) -> Vec<L::Q> {
+        let mut path = nodes.to_vec();
+        path.insert(0, self.start.clone());
+        path.push(self.end.clone());
+        path
+    }
+
+    /// Midpoint rule discrete Lagrangian for `n` interior nodes
+    fn discrete(&self, n: usize) -> DiscreteLagrangian<'a, L> {
+        DiscreteLagrangian::new(self.lagrangian, self.dt(n))
+    }
+
+    /// Midpoint time of the k-th segment
+    fn t_mid(&self, discrete: &DiscreteLagrangian<L>, k: usize) -> f64 {
+        self.t0 + discrete.t_mid(k)
+    }
+
+    /// Whether every segment through `nodes` stays inside the domain of L
+    pub fn is_admissible(&self, nodes: &[L::Q]) -> bool {
+        let discrete = self.discrete(nodes.len());
+        self.path(nodes)
+            .windows(2)
+            .all(|w| discrete.is_admissible(&w[0], &w[1]))
+    }
+
+    /// Discrete action of the k-th segment when `n` interior nodes are used;
+    /// +∞ if the segment leaves the domain of L (e.g. superluminal)
+    pub fn segment_action(&self, n: usize, k: usize, q0: &L::Q, q1: &L::Q) -> f64 {
+        let discrete = self.discrete(n);
+        if discrete.is_admissible(q0, q1) {
+            discrete.calc(self.t_mid(&discrete, k), q0, q1)
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+/// Discrete action; paths leaving the domain of L (e.g. superluminal) get +∞
+pub fn action_nd<L: Lagrangian>(nodes: &[L::Q], problem: &Problem<L>) -> f64 {
+    problem
+        .path(nodes)
+        .windows(2)
+        .enumerate()
+        .map(|(k, w)| problem.segment_action(nodes.len(), k, &w[0], &w[1]))
+        .sum()
+}
+
+/// ∂S/∂q_k of `action_nd` at every interior node
+pub fn residual_nd<L: Lagrangian>(nodes: &[L::Q], problem: &Problem<L>) -> Vec<Vec<f64>> {
+    let discrete = problem.discrete(nodes.len());
+    let q = problem.path(nodes);
+
+    (1..=nodes.len())
+        .map(|k| {
+            let d2 = discrete.d2(problem.t_mid(&discrete, k - 1), &q[k - 1], &q[k]);
+            let d1 = discrete.d1(problem.t_mid(&discrete, k), &q[k], &q[k + 1]);
+            d2.iter().zip(d1).map(|(a, b)| a + b).collect()
+        })
+        .collect()
+}
+
+/// Quantity minimized by the solvers under `mode`; +∞ for paths leaving the domain of L
+pub fn objective_nd<L: Lagrangian>(nodes: &[L::Q], problem: &Problem<L>, mode: SearchMode) -> f64 {
+    match mode {
+        SearchMode::Minimal => action_nd(nodes, problem),
+        SearchMode::Stationary if !problem.is_admissible(nodes) => f64::INFINITY,
+        SearchMode::Stationary => residual_nd(nodes, problem)
+            .iter()
+            .flatten()
+            .map(|r| r.powi(2))
+            .sum(),
+    }
+}
+
+pub fn action_1d<L: Lagrangian<Q = f64>>(nodes: &[f64], problem: &Problem<L>) -> f64 {
+    action_nd(nodes, problem)
+}
+
+pub fn residual_1d<L: Lagrangian<Q = f64>>(nodes: &[f64], problem: &Problem<L>) -> Vec<f64> {
+    residual_nd(nodes, problem).into_iter().flatten().collect()
+}
+
+/// Number of negative eigenvalues of the Hessian of `action_1d` w.r.t. the interior nodes
+pub fn morse_index_1d<L: Lagrangian<Q = f64>>(nodes: &[f64], problem: &Problem<L>) -> usize {
+    let n = nodes.len();
+    let discrete = problem.discrete(n);
+
+    // (∂²L_d/∂q_k², ∂²L_d/∂q_k∂q_{k+1}, ∂²L_d/∂q_{k+1}²) of every segment
+    let blocks = problem
+        .path(nodes)
+        .windows(2)
+        .enumerate()
+        .map(|(k, w)| {
+            let (d11, d12, d22) = discrete.hessian(problem.t_mid(&discrete, k), &w[0], &w[1]);
+            (d11[(0, 0)], d12[(0, 0)], d22[(0, 0)])
+        })
+        .collect_vec();
+
+    let mut h = zeros(n, n);
+    for k in 0..n {
+        h[(k, k)] = blocks[k].2 + blocks[k + 1].0;
+        if k + 1 < n {
+            h[(k, k + 1)] = blocks[k + 1].1;
+            h[(k + 1, k)] = h[(k, k + 1)];
+        }
+    }
+    eigen(&h, EigenMethod::Jacobi)
+        .eigenvalue
+        .iter()
+        .filter(|&&e| e < 0f64)
+        .count()
+}
+
+pub fn objective_1d<L: Lagrangian<Q = f64>>(
+    nodes: &[f64],
+    problem: &Problem<L>,
+    mode: SearchMode,
+) -> f64 {
+    objective_nd(nodes, problem, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::potential::{HarmonicOscillator1D, Mechanical, Potential1D};
+    use reinla::lagrangian::one_dim::{DrivenOscillator, FreeBody, RelativisticParticle};
+    use std::f64::consts::PI;
+
+    fn sho() -> Mechanical<HarmonicOscillator1D> {
+        HarmonicOscillator1D::new(1f64).lagrangian(1f64)
+    }
+
+    #[test]
+    fn action_of_free_straight_line() {
+        // S = m (end - start)² / (2 T)
+        let free = FreeBody::new(2f64);
+        let problem = Problem::new(2f64, 0f64, 4f64, &free);
+        let action = action_1d(&[1f64, 2f64, 3f64], &problem);
+        assert!((action - 8f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn split_halves_time() {
+        let free = FreeBody::new(1f64);
+        let (left, right) = Problem::new(2f64, 0f64, 4f64, &free).split(1.5);
+        assert_eq!(
+            (left.T, left.t0, left.start, left.end),
+            (1f64, 0f64, 0f64, 1.5)
+        );
+        assert_eq!(
+            (right.T, right.t0, right.start, right.end),
+            (1f64, 1f64, 1.5, 4f64)
+        );
+    }
+
+    #[test]
+    fn split_keeps_clock_of_time_dependent_lagrangian() {
+        let driven = DrivenOscillator::new(1f64, 1f64, 2f64, 3f64);
+        let problem = Problem::new(2f64, 0f64, 1f64, &driven);
+        let (left, right) = problem.split(0.7);
+        let whole = action_1d(&[0.3, 0.7, 0.9], &problem);
+        let halves = action_1d(&[0.3], &left) + action_1d(&[0.9], &right);
+        assert!((whole - halves).abs() < 1e-12);
+    }
+
+    #[test]
+    fn residual_vanishes_on_straight_line() {
+        let free = FreeBody::new(1f64);
+        let problem = Problem::new(2f64, 0f64, 4f64, &free);
+        let residual = residual_1d(&[1f64, 2f64, 3f64], &problem);
+        assert!(residual.iter().all(|r| r.abs() < 1e-9));
+
+        let residual = residual_1d(&[1f64, 2.5, 3f64], &problem);
+        assert!(residual[1].abs() > 0.1);
+    }
+
+    #[test]
+    fn morse_index_of_harmonic_oscillator() {
+        // The SHO path is a minimum for ωT < π and a saddle with one negative direction beyond
+        let sho = sho();
+        let nodes = (1..20).map(|i| i as f64 * 0.2).collect_vec();
+        let short = Problem::new(PI / 2f64, 0f64, 4f64, &sho);
+        let long = Problem::new(1.5 * PI, 0f64, 4f64, &sho);
+        assert_eq!(morse_index_1d(&nodes, &short), 0);
+        assert_eq!(morse_index_1d(&nodes, &long), 1);
+    }
+
+    #[test]
+    fn objective_follows_mode() {
+        let free = FreeBody::new(1f64);
+        let problem = Problem::new(2f64, 0f64, 4f64, &free);
+        let nodes = [1f64, 2f64, 3f64];
+        assert_eq!(
+            objective_1d(&nodes, &problem, SearchMode::Minimal),
+            action_1d(&nodes, &problem)
+        );
+        assert!(objective_1d(&nodes, &problem, SearchMode::Stationary) < 1e-12);
+    }
+
+    #[test]
+    fn inadmissible_path_has_infinite_action() {
+        let relativistic = RelativisticParticle::new(1f64, 1f64);
+        let problem = Problem::new(1f64, 0f64, 0.5, &relativistic);
+        assert!(action_1d(&[0.25], &problem).is_finite());
+        assert_eq!(action_1d(&[2f64], &problem), f64::INFINITY);
+        assert_eq!(
+            objective_1d(&[2f64], &problem, SearchMode::Stationary),
+            f64::INFINITY
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::action::{action_1d, Problem, SearchMode};
+use crate::branch_bound::{branch_and_bound_lagrangian_1d, BranchAndBound};
+use crate::pool::NodePool;
+use crate::refine::dynamic_programming_1d;
+use crate::solver::{divide_and_conquer_1d, divide_and_conquer_and_correct_1d, DivideAndConquer};
+use peroxide::fuga::*;
+use reinla::lagrangian::Lagrangian;
+
+/// q(t) of the SHO with q(0) = start and q(T) = end
+///
+/// Unique only if ωT is not a multiple of π
+#[allow(non_snake_case)]
+pub fn sho_solution(omega: f64, T: f64, start: f64, end: f64) -> impl Fn(f64) -> f64 {
+    assert!(
+        (omega * T).sin().abs() > 1e-12,
+        "ωT = {} is a multiple of π",
+        omega * T
+    );
+    move |t| {
+        start * (omega * t).cos()
+            + (end - start * (omega * T).cos()) / (omega * T).sin() * (omega * t).sin()
+    }
+}
+
+/// (L2, L∞) distance of the interior nodes from `exact` sampled at the node times
+///
+/// L2 is weighted by dt, approximating (∫ (q - q_exact)² dt)^(1/2)
+pub fn position_error<L: Lagrangian<Q = f64>, F: Fn(f64) -> f64>(
+    nodes: &[f64],
+    problem: &Problem<L>,
+    exact: F,
+) -> (f64, f64) {
+    let dt = problem.dt(nodes.len());
+    let errors = nodes
+        .iter()
+        .enumerate()
+        .map(|(k, q)| (q - exact(problem.t0 + (k + 1) as f64 * dt)).abs())
+        .collect::<Vec<_>>();
+    let l2 = (dt * errors.iter().map(|e| e.powi(2)).sum::<f64>()).sqrt();
+    let linf = errors.iter().cloned().fold(0f64, f64::max);
+    (l2, linf)
+}
+
+/// Errors of one solver at one depth
+#[derive(Debug, Copy, Clone)]
+pub struct Metrics {
+    pub l2: f64,
+    pub linf: f64,
+    pub action: f64,
+    /// Action above the best increasing path of the pool (the brute force optimum)
+    pub gap: f64,
+    /// Action above the best path of the pool in any order (the dynamic programming optimum)
+    pub gap_dp: f64,
+}
+
+/// Divide and conquer with and without correction at one depth
+#[derive(Debug, Clone)]
+pub struct DepthReport {
+    pub depth: usize,
+    /// Number of interior nodes
+    pub n: usize,
+    pub dc: Metrics,
+    pub dcc: Metrics,
+    /// Best increasing path found by branch and bound; only an upper bound unless `certified`,
+    /// NaN if none was found
+    pub optimum: f64,
+    /// Whether branch and bound finished within its budget, so that `optimum` is exact
+    pub certified: bool,
+    pub optimum_dp: f64,
+    /// Number of nodes the correction step moved
+    pub changed_nodes: usize,
+}
+
+/// Compare `divide_and_conquer_1d` and `divide_and_conquer_and_correct_1d` at every depth with
+/// the analytic solution `exact` and with the exact optima on `node_pool`
+///
+/// The increasing optimum gives up after `max_explored` partial paths per depth and is skipped
+/// when the pool has fewer than n nodes
+pub fn analyze_1d<L: Lagrangian<Q = f64> + Sync, F: Fn(f64) -> f64>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    depths: &[usize],
+    mode: SearchMode,
+    max_explored: u64,
+    exact: F,
+) -> Vec<DepthReport> {
+    let branch_and_bound = BranchAndBound {
+        max_explored: Some(max_explored),
+        ..BranchAndBound::new(true)
+    };
+    depths
+        .iter()
+        .map(|&depth| {
+            let params = DivideAndConquer::new(depth, mode);
+            let dc = divide_and_conquer_1d(node_pool, problem, &params);
+            let dcc = divide_and_conquer_and_correct_1d(node_pool, problem, &params);
+            let n = dc.len();
+
+            // NaN if the pool is too small for n increasing nodes or none was found in budget
+            let (optimum, certified) = if n <= node_pool.len() {
+                branch_and_bound_lagrangian_1d(node_pool, n, problem, &branch_and_bound)
+                    .map_or((f64::NAN, false), |o| (o.action, o.certified))
+            } else {
+                (f64::NAN, false)
+            };
+            let optimum_dp = action_1d(
+                &dynamic_programming_1d(&vec![node_pool.to_vec(); n], problem),
+                problem,
+            );
+
+            let metrics = |nodes: &[f64]| {
+                let (l2, linf) = position_error(nodes, problem, &exact);
+                let action = action_1d(nodes, problem);
+                Metrics {
+                    l2,
+                    linf,
+                    action,
+                    gap: action - optimum,
+                    gap_dp: action - optimum_dp,
+                }
+            };
+
+            DepthReport {
+                depth,
+                n,
+                dc: metrics(&dc),
+                dcc: metrics(&dcc),
+                optimum,
+                certified,
+                optimum_dp,
+                changed_nodes: dc.iter().zip(&dcc).filter(|(a, b)| a != b).count(),
+            }
+        })
+        .collect()
+}
+
+/// Fraction of depths at which the correction step changed the path, `None` without reports
+pub fn correction_frequency(reports: &[DepthReport]) -> Option<f64> {
+    if reports.is_empty() {
+        return None;
+    }
+    let changed = reports.iter().filter(|r| r.changed_nodes > 0).count();
+    Some(changed as f64 / reports.len() as f64)
+}
+
+/// One row per depth, e.g. for `write_parquet`
+pub fn reports_to_dataframe(reports: &[DepthReport]) -> DataFrame {
+    let column = |f: &dyn Fn(&DepthReport) -> f64| Series::new(reports.iter().map(f).collect());
+
+    let mut df = DataFrame::new(vec![]);
+    df.push(
+        "depth",
+        Series::new(reports.iter().map(|r| r.depth as u64).collect::<Vec<u64>>()),
+    );
+    df.push(
+        "n",
+        Series::new(reports.iter().map(|r| r.n as u64).collect::<Vec<u64>>()),
+    );
+    df.push("dc_l2", column(&|r| r.dc.l2));
+    df.push("dc_linf", column(&|r| r.dc.linf));
+    df.push("dc_action", column(&|r| r.dc.action));
+    df.push("dc_gap", column(&|r| r.dc.gap));
+    df.push("dc_gap_dp", column(&|r| r.dc.gap_dp));
+    df.push("dcc_l2", column(&|r| r.dcc.l2));
+    df.push("dcc_linf", column(&|r| r.dcc.linf));
+    df.push("dcc_action", column(&|r| r.dcc.action));
+    df.push("dcc_gap", column(&|r| r.dcc.gap));
+    df.push("dcc_gap_dp", column(&|r| r.dcc.gap_dp));
+    df.push("optimum", column(&|r| r.optimum));
+    df.push(
+        "certified",
+        Series::new(reports.iter().map(|r| r.certified).collect::<Vec<bool>>()),
+    );
+    df.push("optimum_dp", column(&|r| r.optimum_dp));
+    df.push(
+        "changed_nodes",
+        Series::new(
+            reports
+                .iter()
+                .map(|r| r.changed_nodes as u64)
+                .collect::<Vec<u64>>(),
+        ),
+    );
+    df
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::potential::{HarmonicOscillator1D, Potential1D};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn sho_solution_meets_boundary() {
+        let q = sho_solution(2f64, 1f64, 0.5, 3f64);
+        assert!((q(0f64) - 0.5).abs() < 1e-12);
+        assert!((q(1f64) - 3f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn position_error_of_exact_nodes_vanishes() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let exact = sho_solution(1f64, PI / 2f64, 0f64, 2f64);
+        let nodes = (1..=3)
+            .map(|k| exact(k as f64 * PI / 8f64))
+            .collect::<Vec<_>>();
+        let (l2, linf) = position_error(&nodes, &problem, &exact);
+        assert!(l2 < 1e-12 && linf < 1e-12);
+
+        let (l2, linf) = position_error(&[0f64, 0f64, 0f64], &problem, |_| 1f64);
+        assert!((linf - 1f64).abs() < 1e-12);
+        assert!((l2 - (3f64 * PI / 8f64).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn analysis_orders_solvers() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        let exact = sho_solution(1f64, PI / 2f64, 0f64, 2f64);
+        let reports = analyze_1d(
+            &pool,
+            &problem,
+            &[0, 1, 2],
+            SearchMode::Minimal,
+            1_000_000,
+            exact,
+        );
+
+        for r in &reports {
+            assert!(r.certified);
+            assert_eq!(r.n, 2usize.pow(r.depth as u32 + 1) - 1);
+            assert!(r.optimum_dp <= r.optimum + 1e-12);
+            assert!(r.dcc.gap >= -1e-12 && r.dcc.gap <= r.dc.gap + 1e-12);
+            assert!(r.dc.gap_dp >= r.dc.gap - 1e-12);
+        }
+        // A single node is the exact optimum, so the correction has nothing to do
+        assert_eq!(reports[0].changed_nodes, 0);
+        assert!(reports[0].dc.gap.abs() < 1e-12);
+
+        let frequency = correction_frequency(&reports).unwrap();
+        assert!((0f64..=1f64).contains(&frequency));
+        assert_eq!(correction_frequency(&[]), None);
+        let df = reports_to_dataframe(&reports);
+        assert_eq!(df["dcc_gap"].len(), 3);
+    }
+
+    #[test]
+    fn exhausted_budget_is_not_certified() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        let exact = sho_solution(1f64, PI / 2f64, 0f64, 2f64);
+        let reports = analyze_1d(&pool, &problem, &[2], SearchMode::Minimal, 1, exact);
+        assert!(!reports[0].certified);
+    }
+
+    #[test]
+    fn small_pool_skips_increasing_optimum() {
+        // Depth 4 needs more increasing nodes than the 20 in the pool
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        let exact = sho_solution(1f64, PI / 2f64, 0f64, 2f64);
+        let reports = analyze_1d(&pool, &problem, &[4], SearchMode::Minimal, 1_000_000, exact);
+        assert!(reports[0].n > pool.len());
+        assert!(!reports[0].certified);
+        assert!(reports[0].optimum.is_nan() && reports[0].dcc.gap.is_nan());
+        assert!(reports[0].optimum_dp.is_finite());
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of π")]
+    fn sho_solution_rejects_half_period() {
+        let _ = sho_solution(1f64, PI, 0f64, 1f64);
+    }
+}
@@ -0,0 +1,107 @@
+use algola::action::{Problem, SearchMode};
+use algola::pool::NodePool;
+use algola::potential::{HarmonicOscillator1D, Potential1D};
+use algola::solver::{
+    divide_and_conquer_and_correct_1d, divide_and_conquer_and_correct_memo_1d,
+    divide_and_conquer_and_correct_parallel_1d, DivideAndConquer,
+};
+use peroxide::fuga::*;
+use std::env::args;
+use std::f64::consts::PI;
+use std::time::Instant;
+
+const MAX_DEPTH: usize = 10;
+
+/// Usage: dcc_bench [runs] [max serial depth]
+///
+/// Times the plain serial, the memoized serial and the memoized parallel divide and conquer
+/// and correct on the SHO problem of `algola` (200 node pool) and prints hyperfine style CSV
+/// (seconds) with two extra columns: the row each variant is compared with and the speedup
+/// over it. Memoization is measured against plain serial up to `max serial depth`,
+/// parallelism against memoized serial at every depth
+#[allow(non_snake_case)]
+fn main() {
+    let args = args().collect::<Vec<String>>();
+    let runs = args.get(1).map_or(3, |x| x.parse::<usize>().unwrap());
+    let max_serial_depth = args.get(2).map_or(4, |x| x.parse::<usize>().unwrap());
+
+    let A = 0.0;
+    let B = 20.0;
+    let dq = 0.1;
+    let node_pool = NodePool::between(A, B, dq);
+
+    let lagrangian = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+    let problem = Problem::new(PI / 2f64, A, B, &lagrangian);
+
+    println!("command,mean,stddev,median,min,max,baseline,speedup");
+    for depth in 0..=MAX_DEPTH {
+        let params = DivideAndConquer::new(depth, SearchMode::Minimal);
+
+        let (memo, nodes) = bench(runs, || {
+            divide_and_conquer_and_correct_memo_1d(&node_pool, &problem, &params)
+        });
+        let (parallel, parallel_nodes) = bench(runs, || {
+            divide_and_conquer_and_correct_parallel_1d(&node_pool, &problem, &params)
+        });
+        assert_eq!(nodes, parallel_nodes);
+
+        let memo_name = format!("dcc_memo depth={depth}");
+        if depth <= max_serial_depth {
+            let (serial, serial_nodes) = bench(runs, || {
+                divide_and_conquer_and_correct_1d(&node_pool, &problem, &params)
+            });
+            assert_eq!(nodes, serial_nodes);
+            let serial_name = format!("dcc_serial depth={depth}");
+            println!("{}", csv_row(&serial_name, &serial, None));
+            println!(
+                "{}",
+                csv_row(&memo_name, &memo, Some((&serial_name, &serial)))
+            );
+        } else {
+            println!("{}", csv_row(&memo_name, &memo, None));
+        }
+        println!(
+            "{}",
+            csv_row(
+                &format!("dcc_parallel depth={depth}"),
+                &parallel,
+                Some((&memo_name, &memo))
+            )
+        );
+    }
+}
+
+/// Wall-clock seconds of `runs` calls and the result of the last one
+fn bench<F: Fn() -> Vec<f64>>(runs: usize, f: F) -> (Vec<f64>, Vec<f64>) {
+    let mut times = vec![];
+    let mut result = vec![];
+    for _ in 0..runs {
+        let now = Instant::now();
+        result = f();
+        times.push(now.elapsed().as_secs_f64());
+    }
+    (times, result)
+}
+
+/// One CSV row, with the speedup over `baseline` (name, times) if given
+fn csv_row(command: &str, times: &[f64], baseline: Option<(&str, &[f64])>) -> String {
+    let times = times.to_vec();
+    let (baseline, speedup) = match baseline {
+        Some((name, base)) => (
+            name.to_string(),
+            format!("{}", base.to_vec().mean() / times.mean()),
+        ),
+        None => (String::new(), String::new()),
+    };
+    format!(
+        "{},{},{},{},{},{},{},{}",
+        command,
+        times.mean(),
+        if times.len() > 1 { times.sd() } else { 0f64 },
+        times.median(),
+        times.min(),
+        times.max(),
+        baseline,
+        speedup
+    )
+}
@@ -0,0 +1,58 @@
+use algola::action::Problem;
+use algola::branch_bound::{
+    branch_and_bound_1d, BranchAndBound, LowerBound, MechanicalBound, RelaxedBound, SegmentBound,
+};
+use algola::pool::NodePool;
+use algola::potential::{HarmonicOscillator1D, Potential1D};
+use std::f64::consts::PI;
+use std::time::Instant;
+
+const MAX_EXPLORED: u64 = 1_000_000;
+
+/// Exact monotone optimum of the SHO problem of `algola` (200 node pool) for growing N with
+/// each lower bound; searches longer than `MAX_EXPLORED` end without a certificate
+#[allow(non_snake_case)]
+fn main() {
+    let A = 0.0;
+    let B = 20.0;
+    let node_pool = NodePool::between(A, B, 0.1);
+    let lagrangian = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+    let problem = Problem::new(PI / 2f64, A, B, &lagrangian);
+    let params = BranchAndBound {
+        max_explored: Some(MAX_EXPLORED),
+        ..BranchAndBound::new(true)
+    };
+
+    println!("bound,N,action,explored,pruned,certified,seconds");
+    for N in [1, 3, 7, 15] {
+        let bounds: Vec<(&str, Box<dyn LowerBound>)> = vec![
+            (
+                "segment",
+                Box::new(SegmentBound::new(&node_pool, N, &problem)),
+            ),
+            (
+                "mechanical",
+                Box::new(MechanicalBound::new(&node_pool, N, &problem)),
+            ),
+            (
+                "relaxed",
+                Box::new(RelaxedBound::new(&node_pool, N, &problem)),
+            ),
+        ];
+        for (name, bound) in bounds {
+            let now = Instant::now();
+            let optimum =
+                branch_and_bound_1d(&node_pool, N, &problem, bound.as_ref(), &params).unwrap();
+            println!(
+                "{},{},{},{},{},{},{}",
+                name,
+                N,
+                optimum.action,
+                optimum.explored,
+                optimum.pruned,
+                optimum.certified,
+                now.elapsed().as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use algola::action::Problem;
+use algola::pool::NodePool;
+use algola::potential::{HarmonicOscillator1D, Potential1D};
+use algola::refine::{refine_1d, Refinement, Tolerance};
+use std::f64::consts::PI;
+
+/// Coarse-to-fine solve of the SHO problem of `algola`, starting from a pool with dq = 1
+#[allow(non_snake_case)]
+fn main() {
+    let A = 0.0;
+    let B = 20.0;
+    let T = PI / 2f64;
+    let N = 15;
+
+    let lagrangian = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+    let problem = Problem::new(T, A, B, &lagrangian);
+    let params = Refinement::new(N, Tolerance::Position(1e-5));
+    let refined = refine_1d(&NodePool::uniform(A, 1f64, 21), &problem, &params);
+
+    // Deviation from q(t) = B sin(t) / sin(T)
+    let dt = problem.dt(N);
+    let q_true = (1..=N)
+        .map(|k| B * (k as f64 * dt).sin() / T.sin())
+        .collect::<Vec<_>>();
+
+    println!("level,dq,action,action_change,position_change,recentres,on_boundary,max_error");
+    for (l, level) in refined.levels.iter().enumerate() {
+        let max_error = level
+            .nodes
+            .iter()
+            .zip(&q_true)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0f64, f64::max);
+        println!(
+            "{},{},{},{},{},{},{},{}",
+            l,
+            level.dq,
+            level.action,
+            level.action_change.map_or(String::new(), |e| e.to_string()),
+            level
+                .position_change
+                .map_or(String::new(), |e| e.to_string()),
+            level.recentres,
+            level.on_boundary,
+            max_error
+        );
+    }
+    if !refined.converged {
+        eprintln!("tolerance not met after {} levels", params.max_levels);
+    }
+}
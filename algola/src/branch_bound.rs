@@ -0,0 +1,420 @@
+use crate::action::Problem;
+use crate::bruteforce::cost_to_go_1d;
+use crate::pool::NodePool;
+use crate::potential::{Mechanical, Potential1D};
+use itertools::Itertools;
+use reinla::lagrangian::Lagrangian;
+
+/// Admissible lower bound of the action left after an interior node
+pub trait LowerBound {
+    /// Bound of the action of segments k + 1..=n when the k-th interior node is pool node `i`
+    /// at `q`
+    fn remaining(&self, k: usize, i: usize, q: f64) -> f64;
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Bounds
+// └──────────────────────────────────────────────────────────┘
+/// Sum of the least action of every remaining segment over all node pairs; any Lagrangian
+#[derive(Debug, Clone)]
+pub struct SegmentBound {
+    /// suffix[k]: Σ_{j > k} min_{a, b} S_j(a, b)
+    suffix: Vec<f64>,
+}
+
+impl SegmentBound {
+    pub fn new<L: Lagrangian<Q = f64>>(
+        node_pool: &NodePool,
+        n: usize,
+        problem: &Problem<L>,
+    ) -> Self {
+        let values = node_pool.to_vec();
+        let least = |k: usize, from: &[f64], to: &[f64]| {
+            from.iter()
+                .cartesian_product(to)
+                .map(|(a, b)| problem.segment_action(n, k, a, b))
+                .fold(f64::INFINITY, f64::min)
+        };
+
+        // Segment n ends at the end point, the others between pool nodes
+        let mut suffix = vec![0f64; n + 1];
+        for k in (0..n).rev() {
+            let to = if k + 1 == n {
+                vec![problem.end]
+            } else {
+                values.clone()
+            };
+            suffix[k] = suffix[k + 1] + least(k + 1, &values, &to);
+        }
+        Self { suffix }
+    }
+}
+
+impl LowerBound for SegmentBound {
+    fn remaining(&self, k: usize, _i: usize, _q: f64) -> f64 {
+        self.suffix[k]
+    }
+}
+
+/// Straight-line kinetic cost plus the deepest potential over the node pool, for
+/// L = m q̇² / 2 - V(q)
+///
+/// Over r segments of length dt, Σ m Δq² / (2 dt) ≥ m (end - q)² / (2 r dt) and every
+/// midpoint potential is at most the maximum of V over the midpoints of all node pairs
+#[derive(Debug, Clone)]
+pub struct MechanicalBound {
+    mass: f64,
+    n: usize,
+    dt: f64,
+    end: f64,
+    v_max: f64,
+}
+
+impl MechanicalBound {
+    pub fn new<P: Potential1D>(
+        node_pool: &NodePool,
+        n: usize,
+        problem: &Problem<Mechanical<P>>,
+    ) -> Self {
+        let mut values = node_pool.to_vec();
+        values.extend([problem.start, problem.end]);
+        let potential = problem.lagrangian.get_potential();
+        let v_max = values
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| potential.eval(0.5 * (a + b)))
+            .chain(values.iter().map(|&q| potential.eval(q)))
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        Self {
+            mass: problem.lagrangian.get_mass(),
+            n,
+            dt: problem.dt(n),
+            end: problem.end,
+            v_max,
+        }
+    }
+}
+
+impl LowerBound for MechanicalBound {
+    fn remaining(&self, k: usize, _i: usize, q: f64) -> f64 {
+        let r = (self.n - k) as f64;
+        0.5 * self.mass * (self.end - q).powi(2) / (r * self.dt) - r * self.dt * self.v_max
+    }
+}
+
+/// Exact least action to the end point when the nodes may come in any order (`cost_to_go_1d`);
+/// the tightest of the bounds at O(n m²) set-up cost
+#[derive(Debug, Clone)]
+pub struct RelaxedBound {
+    cost: Vec<Vec<f64>>,
+}
+
+impl RelaxedBound {
+    pub fn new<L: Lagrangian<Q = f64> + Sync>(
+        node_pool: &NodePool,
+        n: usize,
+        problem: &Problem<L>,
+    ) -> Self {
+        Self {
+            cost: cost_to_go_1d(&node_pool.to_vec(), n, problem),
+        }
+    }
+}
+
+impl LowerBound for RelaxedBound {
+    fn remaining(&self, k: usize, i: usize, _q: f64) -> f64 {
+        self.cost[k][i]
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Branch and bound
+// └──────────────────────────────────────────────────────────┘
+/// Options of `branch_and_bound_1d`
+#[derive(Debug, Copy, Clone)]
+pub struct BranchAndBound {
+    /// Only strictly increasing pool indices, as in `bruteforce_1d`
+    pub monotone: bool,
+    /// Give up after expanding this many partial paths
+    pub max_explored: Option<u64>,
+}
+
+impl BranchAndBound {
+    pub fn new(monotone: bool) -> Self {
+        Self {
+            monotone,
+            max_explored: None,
+        }
+    }
+}
+
+/// Result of `branch_and_bound_1d`
+#[derive(Debug, Clone)]
+pub struct Optimum {
+    pub nodes: Vec<f64>,
+    pub action: f64,
+    /// Number of partial paths expanded
+    pub explored: u64,
+    /// Number of partial paths discarded by the bound
+    pub pruned: u64,
+    /// Whether the search finished, so that no path has a smaller action
+    pub certified: bool,
+}
+
+struct Search<'a, 'p, L: Lagrangian<Q = f64>, B: LowerBound + ?Sized> {
+    values: &'a [f64],
+    n: usize,
+    problem: &'a Problem<'p, L>,
+    bound: &'a B,
+    params: &'a BranchAndBound,
+    path: Vec<usize>,
+    best: Option<(f64, Vec<usize>)>,
+    explored: u64,
+    pruned: u64,
+    aborted: bool,
+}
+
+impl<L: Lagrangian<Q = f64>, B: LowerBound + ?Sized> Search<'_, '_, L, B> {
+    fn incumbent(&self) -> f64 {
+        self.best.as_ref().map_or(f64::INFINITY, |b| b.0)
+    }
+
+    /// Expand the partial path ending at `prev` with action `partial` by its k-th node
+    fn expand(&mut self, k: usize, prev: f64, partial: f64) {
+        if self
+            .params
+            .max_explored
+            .is_some_and(|max| self.explored >= max)
+        {
+            self.aborted = true;
+            return;
+        }
+        self.explored += 1;
+
+        if k == self.n {
+            let action = partial
+                + self
+                    .problem
+                    .segment_action(self.n, k, &prev, &self.problem.end);
+            if action < self.incumbent() {
+                self.best = Some((action, self.path.clone()));
+            }
+            return;
+        }
+
+        let m = self.values.len();
+        let range = if self.params.monotone {
+            self.path.last().map_or(0, |&i| i + 1)..m + k + 1 - self.n
+        } else {
+            0..m
+        };
+
+        // Most promising child first, so that a good incumbent is found early
+        let children = range
+            .map(|i| {
+                let q = self.values[i];
+                let partial = partial + self.problem.segment_action(self.n, k, &prev, &q);
+                (partial + self.bound.remaining(k, i, q), partial, i)
+            })
+            .sorted_by(|a, b| a.0.total_cmp(&b.0))
+            .collect_vec();
+
+        for (j, (lower, partial, i)) in children.iter().enumerate() {
+            if *lower >= self.incumbent() {
+                self.pruned += (children.len() - j) as u64;
+                break;
+            }
+            self.path.push(*i);
+            self.expand(k + 1, self.values[*i], *partial);
+            self.path.pop();
+            if self.aborted {
+                return;
+            }
+        }
+    }
+}
+
+/// Path of least action through `n` nodes of `node_pool` by depth first branch and bound
+///
+/// Partial paths whose action plus `bound` cannot beat the best complete path are discarded, so
+/// the result is exact whenever `bound` is admissible and the search is not cut by `max_explored`.
+/// Returns `None` if no admissible path was found, either because none exists or because
+/// `max_explored` ran out first
+pub fn branch_and_bound_1d<L: Lagrangian<Q = f64>, B: LowerBound + ?Sized>(
+    node_pool: &NodePool,
+    n: usize,
+    problem: &Problem<L>,
+    bound: &B,
+    params: &BranchAndBound,
+) -> Option<Optimum> {
+    let values = node_pool.to_vec();
+    assert!(
+        !params.monotone || n <= values.len(),
+        "need n <= {} nodes for a monotone path, got {}",
+        values.len(),
+        n
+    );
+
+    let mut search = Search {
+        values: &values,
+        n,
+        problem,
+        bound,
+        params,
+        path: Vec::with_capacity(n),
+        best: None,
+        explored: 0,
+        pruned: 0,
+        aborted: false,
+    };
+    search.expand(0, problem.start, 0f64);
+
+    let (action, path) = search.best?;
+    Some(Optimum {
+        nodes: path.into_iter().map(|i| values[i]).collect(),
+        action,
+        explored: search.explored,
+        pruned: search.pruned,
+        certified: !search.aborted,
+    })
+}
+
+/// `branch_and_bound_1d` with a `RelaxedBound`, for any Lagrangian
+pub fn branch_and_bound_lagrangian_1d<L: Lagrangian<Q = f64> + Sync>(
+    node_pool: &NodePool,
+    n: usize,
+    problem: &Problem<L>,
+    params: &BranchAndBound,
+) -> Option<Optimum> {
+    let bound = RelaxedBound::new(node_pool, n, problem);
+    branch_and_bound_1d(node_pool, n, problem, &bound, params)
+}
+
+/// `branch_and_bound_1d` with a `MechanicalBound`, for a `Potential1D` through `Mechanical`
+pub fn branch_and_bound_potential_1d<P: Potential1D>(
+    node_pool: &NodePool,
+    n: usize,
+    problem: &Problem<Mechanical<P>>,
+    params: &BranchAndBound,
+) -> Option<Optimum> {
+    let bound = MechanicalBound::new(node_pool, n, problem);
+    branch_and_bound_1d(node_pool, n, problem, &bound, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::action_1d;
+    use crate::action::SearchMode;
+    use crate::potential::HarmonicOscillator1D;
+    use crate::refine::dynamic_programming_1d;
+    use crate::solver::bruteforce_1d;
+    use reinla::lagrangian::one_dim::{DrivenOscillator, RelativisticParticle};
+    use reinla::potential::DoubleWell;
+    use std::f64::consts::PI;
+
+    fn sho() -> Mechanical<HarmonicOscillator1D> {
+        HarmonicOscillator1D::new(1f64).lagrangian(1f64)
+    }
+
+    #[test]
+    fn monotone_matches_bruteforce() {
+        let l = sho();
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        let bf = bruteforce_1d(&pool, 4, &problem, SearchMode::Minimal).unwrap();
+        let params = BranchAndBound::new(true);
+        let segment = SegmentBound::new(&pool, 4, &problem);
+        for optimum in [
+            branch_and_bound_1d(&pool, 4, &problem, &segment, &params),
+            branch_and_bound_lagrangian_1d(&pool, 4, &problem, &params),
+            branch_and_bound_potential_1d(&pool, 4, &problem, &params),
+        ] {
+            let optimum = optimum.unwrap();
+            assert!(optimum.certified);
+            assert!((optimum.action - action_1d(&bf, &problem)).abs() < 1e-12);
+            // Brute force evaluates C(20, 4) = 4845 paths
+            assert!(optimum.explored < 4845);
+        }
+    }
+
+    #[test]
+    fn free_order_matches_dynamic_programming() {
+        // Double well crossing, compared with the exact dynamic programming optimum
+        let l = DoubleWell::new(1f64, 1f64).lagrangian(2f64);
+        let problem = Problem::new(3f64, -1f64, 1f64, &l);
+        let pool = NodePool::between(-1.5, 1.5, 0.25);
+        let dp = dynamic_programming_1d(&vec![pool.to_vec(); 5], &problem);
+        let optimum =
+            branch_and_bound_potential_1d(&pool, 5, &problem, &BranchAndBound::new(false)).unwrap();
+        assert!(optimum.certified);
+        assert!((optimum.action - action_1d(&dp, &problem)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn time_dependent_lagrangian() {
+        let l = DrivenOscillator::new(1f64, 1f64, 1f64, 2f64);
+        let problem = Problem::new(2f64, 0f64, 1f64, &l);
+        let pool = NodePool::between(-1f64, 2f64, 0.25);
+        let dp = dynamic_programming_1d(&vec![pool.to_vec(); 4], &problem);
+        let optimum =
+            branch_and_bound_lagrangian_1d(&pool, 4, &problem, &BranchAndBound::new(false))
+                .unwrap();
+        assert!((optimum.action - action_1d(&dp, &problem)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bounds_are_admissible() {
+        let l = DoubleWell::new(1f64, 1f64).lagrangian(1f64);
+        let problem = Problem::new(2f64, -1f64, 1f64, &l);
+        let pool = NodePool::between(-1.5, 1.5, 0.5);
+        let mechanical = MechanicalBound::new(&pool, 3, &problem);
+        let segment = SegmentBound::new(&pool, 3, &problem);
+        let relaxed = RelaxedBound::new(&pool, 3, &problem);
+
+        // Every continuation after the first interior node
+        let values = pool.to_vec();
+        for (i, &q0) in values.iter().enumerate() {
+            for (&q1, &q2) in values.iter().cartesian_product(&values) {
+                let rest = problem.segment_action(3, 1, &q0, &q1)
+                    + problem.segment_action(3, 2, &q1, &q2)
+                    + problem.segment_action(3, 3, &q2, &problem.end);
+                assert!(mechanical.remaining(0, i, q0) <= rest + 1e-12);
+                assert!(segment.remaining(0, i, q0) <= rest + 1e-12);
+                assert!(relaxed.remaining(0, i, q0) <= rest + 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn node_limit_drops_certificate() {
+        let l = sho();
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        let mut params = BranchAndBound {
+            max_explored: Some(10),
+            ..BranchAndBound::new(true)
+        };
+        // The first complete path takes 5 expansions
+        let optimum = branch_and_bound_potential_1d(&pool, 4, &problem, &params).unwrap();
+        assert!(!optimum.certified);
+        assert_eq!(optimum.explored, 10);
+
+        params.max_explored = Some(3);
+        assert!(branch_and_bound_potential_1d(&pool, 4, &problem, &params).is_none());
+    }
+
+    #[test]
+    fn no_admissible_path() {
+        // Every path from 0 to 2 in T = 1 is superluminal for c = 1
+        let l = RelativisticParticle::new(1f64, 1f64);
+        let problem = Problem::new(1f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.25);
+        let segment = SegmentBound::new(&pool, 3, &problem);
+        for monotone in [true, false] {
+            let params = BranchAndBound::new(monotone);
+            assert!(branch_and_bound_1d(&pool, 3, &problem, &segment, &params).is_none());
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use crate::action::{objective_1d, Problem, SearchMode};
+use crate::pool::NodePool;
+use rayon::prelude::*;
+use reinla::lagrangian::Lagrangian;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Options of `bruteforce_1d_streaming`
+#[derive(Debug, Copy, Clone)]
+pub struct BruteForce {
+    pub mode: SearchMode,
+    /// Print a line to stderr whenever a chunk (all paths sharing a first node) is done
+    pub progress: bool,
+    /// Skip every path whose partial action plus a lower bound of the rest cannot beat the
+    /// best path so far; only applies to `SearchMode::Minimal`
+    pub prune: bool,
+}
+
+impl BruteForce {
+    pub fn new(mode: SearchMode) -> Self {
+        Self {
+            mode,
+            progress: false,
+            prune: true,
+        }
+    }
+}
+
+/// Best path found by an exhaustive search
+#[derive(Debug, Clone)]
+pub struct Search {
+    pub nodes: Vec<f64>,
+    pub objective: f64,
+    /// Number of complete paths evaluated
+    pub evaluated: u64,
+    /// Number of partial paths cut off by the lower bound
+    pub pruned: u64,
+}
+
+/// Least action from the k-th interior node at every pool node to the end point, relaxing the
+/// ordering of the nodes; a lower bound of the remaining action of any increasing path
+pub fn cost_to_go_1d<L: Lagrangian<Q = f64> + Sync>(
+    values: &[f64],
+    n: usize,
+    problem: &Problem<L>,
+) -> Vec<Vec<f64>> {
+    let mut cost = vec![vec![]; n];
+    if n == 0 {
+        return cost;
+    }
+    cost[n - 1] = values
+        .iter()
+        .map(|q| problem.segment_action(n, n, q, &problem.end))
+        .collect();
+    for k in (0..n - 1).rev() {
+        cost[k] = values
+            .par_iter()
+            .map(|q0| {
+                values
+                    .iter()
+                    .zip(&cost[k + 1])
+                    .map(|(q1, c)| problem.segment_action(n, k + 1, q0, q1) + c)
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .collect();
+    }
+    cost
+}
+
+/// Lower `bound` to `x` if smaller
+fn lower_bound(bound: &AtomicU64, x: f64) {
+    let _ = bound.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |b| {
+        (x < f64::from_bits(b)).then_some(x.to_bits())
+    });
+}
+
+/// Depth first enumeration of the increasing index paths under one first node
+struct Enumerator<'a, 'p, L: Lagrangian<Q = f64>> {
+    values: &'a [f64],
+    n: usize,
+    problem: &'a Problem<'p, L>,
+    mode: SearchMode,
+    cost_to_go: Option<&'a [Vec<f64>]>,
+    bound: &'a AtomicU64,
+    path: Vec<usize>,
+    best: Option<(f64, Vec<usize>)>,
+    evaluated: u64,
+    pruned: u64,
+}
+
+impl<L: Lagrangian<Q = f64>> Enumerator<'_, '_, L> {
+    /// Extend `path` (ending at `prev` with action `partial`) by every k-th node from `from..`
+    fn visit(&mut self, k: usize, prev: f64, from: usize, partial: f64) {
+        for i in from..=self.values.len() - (self.n - k) {
+            self.visit_node(k, i, prev, partial);
+        }
+    }
+
+    /// Extend `path` by pool node `i` as the k-th node
+    fn visit_node(&mut self, k: usize, i: usize, prev: f64, partial: f64) {
+        let q = self.values[i];
+        let partial = match self.mode {
+            SearchMode::Minimal => partial + self.problem.segment_action(self.n, k, &prev, &q),
+            SearchMode::Stationary => 0f64,
+        };
+        if let Some(cost) = self.cost_to_go {
+            // Strict with some slack so that ties with the best path are never cut
+            let best = f64::from_bits(self.bound.load(Ordering::Relaxed));
+            if partial + cost[k][i] > best + 1e-12 * best.abs().max(1f64) {
+                self.pruned += 1;
+                return;
+            }
+        }
+
+        self.path.push(i);
+        if k + 1 == self.n {
+            let objective = match self.mode {
+                SearchMode::Minimal => {
+                    partial
+                        + self
+                            .problem
+                            .segment_action(self.n, self.n, &q, &self.problem.end)
+                }
+                SearchMode::Stationary => {
+                    let nodes = self
+                        .path
+                        .iter()
+                        .map(|&j| self.values[j])
+                        .collect::<Vec<_>>();
+                    objective_1d(&nodes, self.problem, self.mode)
+                }
+            };
+            self.evaluated += 1;
+            // Paths leaving the domain of L (+∞) are never the best, as in `bruteforce_1d`
+            if objective < self.best.as_ref().map_or(f64::INFINITY, |b| b.0) {
+                self.best = Some((objective, self.path.clone()));
+                lower_bound(self.bound, objective);
+            }
+        } else {
+            self.visit(k + 1, q, i + 1, partial);
+        }
+        self.path.pop();
+    }
+}
+
+/// Best `n` increasing nodes from `node_pool` without materializing the combinations, or
+/// `None` if every path leaves the domain of L
+///
+/// Paths sharing a first node form a chunk; chunks run in parallel with one path of memory each.
+/// Ties go to the lexicographically first path, as in `bruteforce_1d`
+pub fn bruteforce_1d_streaming<L: Lagrangian<Q = f64> + Sync>(
+    node_pool: &NodePool,
+    n: usize,
+    problem: &Problem<L>,
+    params: &BruteForce,
+) -> Option<Search> {
+    let values = node_pool.to_vec();
+    let m = values.len();
+    assert!(0 < n && n <= m, "need 0 < n <= {} nodes, got {}", m, n);
+
+    let cost_to_go = (params.prune && params.mode == SearchMode::Minimal)
+        .then(|| cost_to_go_1d(&values, n, problem));
+    let bound = AtomicU64::new(f64::INFINITY.to_bits());
+    let done = AtomicUsize::new(0);
+    let chunks = m - n + 1;
+
+    let results = (0..chunks)
+        .into_par_iter()
+        .map(|first| {
+            let mut enumerator = Enumerator {
+                values: &values,
+                n,
+                problem,
+                mode: params.mode,
+                cost_to_go: cost_to_go.as_deref(),
+                bound: &bound,
+                path: Vec::with_capacity(n),
+                best: None,
+                evaluated: 0,
+                pruned: 0,
+            };
+            enumerator.visit_node(0, first, problem.start, 0f64);
+            if params.progress {
+                let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                eprintln!("bruteforce: {}/{} chunks", done, chunks);
+            }
+            (enumerator.best, enumerator.evaluated, enumerator.pruned)
+        })
+        .collect::<Vec<_>>();
+
+    let mut best: Option<(f64, Vec<usize>)> = None;
+    let (mut evaluated, mut pruned) = (0, 0);
+    for (chunk_best, e, p) in results {
+        evaluated += e;
+        pruned += p;
+        if let Some((objective, path)) = chunk_best {
+            if best.as_ref().is_none_or(|b| objective < b.0) {
+                best = Some((objective, path));
+            }
+        }
+    }
+
+    let (objective, path) = best?;
+    Some(Search {
+        nodes: path.into_iter().map(|i| values[i]).collect(),
+        objective,
+        evaluated,
+        pruned,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::action_1d;
+    use crate::potential::{HarmonicOscillator1D, Potential1D};
+    use crate::solver::bruteforce_1d;
+    use reinla::lagrangian::Lagrangian;
+    use std::f64::consts::PI;
+
+    fn binomial(m: u64, n: u64) -> u64 {
+        (0..n).fold(1, |acc, i| acc * (m - i) / (i + 1))
+    }
+
+    #[test]
+    fn streaming_matches_bruteforce() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        for mode in [SearchMode::Minimal, SearchMode::Stationary] {
+            for prune in [false, true] {
+                let params = BruteForce {
+                    prune,
+                    ..BruteForce::new(mode)
+                };
+                let search = bruteforce_1d_streaming(&pool, 3, &problem, &params);
+                assert_eq!(
+                    search.map(|s| s.nodes),
+                    bruteforce_1d(&pool, 3, &problem, mode)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn without_pruning_every_path_is_evaluated() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        let params = BruteForce {
+            prune: false,
+            ..BruteForce::new(SearchMode::Minimal)
+        };
+        let search = bruteforce_1d_streaming(&pool, 4, &problem, &params).unwrap();
+        assert_eq!(search.evaluated, binomial(20, 4));
+        assert_eq!(search.pruned, 0);
+        assert_eq!(search.objective, action_1d(&search.nodes, &problem));
+    }
+
+    #[test]
+    fn pruning_cuts_most_paths() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 20f64, &l);
+        let pool = NodePool::between(0f64, 20f64, 0.5);
+        let params = BruteForce::new(SearchMode::Minimal);
+        let search = bruteforce_1d_streaming(&pool, 4, &problem, &params).unwrap();
+        assert!(search.pruned > 0);
+        assert!(search.evaluated < binomial(40, 4) / 100);
+
+        // Same optimum as an exact search over the same increasing paths
+        let exact = bruteforce_1d_streaming(
+            &pool,
+            4,
+            &problem,
+            &BruteForce {
+                prune: false,
+                ..params
+            },
+        )
+        .unwrap();
+        assert_eq!(search.nodes, exact.nodes);
+    }
+
+    #[test]
+    fn cost_to_go_bounds_remaining_action() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let values = NodePool::between(0f64, 2f64, 0.25).to_vec();
+        let cost = cost_to_go_1d(&values, 3, &problem);
+        // Remaining action of 0.5 -> 1.5 -> end, from the first interior node on
+        let rest = problem.segment_action(3, 1, &values[1], &values[5])
+            + problem.segment_action(3, 2, &values[5], &values[6])
+            + problem.segment_action(3, 3, &values[6], &problem.end);
+        assert!(cost[0][1] <= rest);
+    }
+
+    /// Free particle restricted to speeds below 1; L stays finite outside its domain, so
+    /// only the admissibility check can reject a path
+    struct Subluminal;
+
+    impl Lagrangian for Subluminal {
+        type Q = f64;
+
+        fn calc(&self, _q: &f64, dq: &f64) -> f64 {
+            0.5 * dq.powi(2)
+        }
+
+        fn is_admissible(&self, _q: &f64, dq: &f64) -> bool {
+            dq.abs() < 1f64
+        }
+    }
+
+    #[test]
+    fn no_admissible_path() {
+        // Every path from 0 to 2 in T = 1 is faster than 1 on some segment
+        let l = Subluminal;
+        let problem = Problem::new(1f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.25);
+        for mode in [SearchMode::Minimal, SearchMode::Stationary] {
+            assert!(bruteforce_1d(&pool, 3, &problem, mode).is_none());
+            assert!(bruteforce_1d_streaming(&pool, 3, &problem, &BruteForce::new(mode)).is_none());
+        }
+    }
+}
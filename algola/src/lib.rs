@@ -0,0 +1,8 @@
+pub mod action;
+pub mod analysis;
+pub mod branch_bound;
+pub mod bruteforce;
+pub mod pool;
+pub mod potential;
+pub mod refine;
+pub mod solver;
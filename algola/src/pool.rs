@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+/// Sorted candidate positions of a 1D node, addressed by index
+///
+/// Only built through `uniform`, `between` and `values`, which keep the nodes finite,
+/// strictly increasing and distinct
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodePool(Pool);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Pool {
+    /// start + i * spacing for i in 0..count
+    Uniform {
+        start: f64,
+        spacing: f64,
+        count: usize,
+    },
+    /// Arbitrary values in increasing order
+    Values(Vec<f64>),
+}
+
+impl NodePool {
+    pub fn uniform(start: f64, spacing: f64, count: usize) -> Self {
+        assert!(start.is_finite(), "start must be finite");
+        assert!(
+            spacing > 0f64 && spacing.is_finite(),
+            "spacing must be positive and finite"
+        );
+        NodePool(Pool::Uniform {
+            start,
+            spacing,
+            count,
+        })
+    }
+
+    /// a + dq, a + 2 dq, .. up to b, i.e. the nodes strictly inside (a, b] for any dq
+    pub fn between(a: f64, b: f64, dq: f64) -> Self {
+        let count = ((b - a) / dq + 1e-9).floor() as usize;
+        Self::uniform(a + dq, dq, count)
+    }
+
+    /// Non-uniform pool; `values` are sorted and deduplicated and must be finite
+    pub fn values(mut values: Vec<f64>) -> Self {
+        assert!(
+            values.iter().all(|x| x.is_finite()),
+            "pool values must be finite"
+        );
+        values.sort_by(|a, b| a.total_cmp(b));
+        values.dedup();
+        NodePool(Pool::Values(values))
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            Pool::Uniform { count, .. } => *count,
+            Pool::Values(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Largest gap between consecutive nodes, i.e. the spacing of a uniform pool
+    pub fn spacing(&self) -> f64 {
+        match &self.0 {
+            Pool::Uniform { spacing, .. } => *spacing,
+            Pool::Values(values) => values.windows(2).map(|w| w[1] - w[0]).fold(0f64, f64::max),
+        }
+    }
+
+    /// i-th node
+    pub fn get(&self, i: usize) -> f64 {
+        match &self.0 {
+            Pool::Uniform {
+                start,
+                spacing,
+                count,
+            } => {
+                assert!(i < *count, "index {} out of pool of {}", i, count);
+                start + i as f64 * spacing
+            }
+            Pool::Values(values) => values[i],
+        }
+    }
+
+    /// Nodes with indices in `range`
+    pub fn slice(&self, range: Range<usize>) -> Vec<f64> {
+        range.map(|i| self.get(i)).collect()
+    }
+
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.slice(0..self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn between_counts_any_spacing() {
+        assert_eq!(NodePool::between(0f64, 20f64, 0.1).len(), 200);
+        assert_eq!(NodePool::between(0f64, 20f64, 0.01).len(), 2000);
+
+        let third = NodePool::between(0f64, 20f64, 1f64 / 3f64);
+        assert_eq!(third.len(), 60);
+        assert!((third.get(59) - 20f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn values_are_sorted() {
+        let pool = NodePool::values(vec![3f64, 1f64, 2f64, 1f64]);
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.slice(1..3), vec![2f64, 3f64]);
+    }
+
+    #[test]
+    #[should_panic(expected = "finite")]
+    fn values_reject_nan() {
+        NodePool::values(vec![1f64, f64::NAN]);
+    }
+
+    #[test]
+    #[should_panic(expected = "spacing")]
+    fn uniform_rejects_nan_spacing() {
+        NodePool::uniform(0f64, f64::NAN, 3);
+    }
+}
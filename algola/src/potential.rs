@@ -0,0 +1,120 @@
+use peroxide::fuga::*;
+use reinla::lagrangian::Lagrangian;
+use reinla::potential::Potential;
+
+pub trait Potential1D: Clone {
+    fn eval(&self, q: f64) -> f64;
+
+    /// Same as `eval` but on dual numbers; `None` falls back to finite differences
+    fn eval_ad(&self, _q: AD) -> Option<AD> {
+        None
+    }
+
+    /// L = m q̇² / 2 - V(q)
+    fn lagrangian(self, mass: f64) -> Mechanical<Self> {
+        Mechanical::new(mass, self)
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone)]
+pub struct HarmonicOscillator1D {
+    omega: f64,
+    omega2: f64,
+}
+
+impl HarmonicOscillator1D {
+    pub fn new(omega: f64) -> HarmonicOscillator1D {
+        HarmonicOscillator1D {
+            omega,
+            omega2: omega.powi(2),
+        }
+    }
+}
+
+impl Potential1D for HarmonicOscillator1D {
+    fn eval(&self, q: f64) -> f64 {
+        0.5 * self.omega2 * q.powi(2)
+    }
+
+    fn eval_ad(&self, q: AD) -> Option<AD> {
+        Some(0.5 * self.omega2 * q.powi(2))
+    }
+}
+
+/// Every reinla potential (Anharmonic, Duffing, Morse, DoubleWell, PoschlTeller)
+impl<P: Potential + Clone> Potential1D for P {
+    fn eval(&self, q: f64) -> f64 {
+        Potential::eval(self, q)
+    }
+
+    fn eval_ad(&self, q: AD) -> Option<AD> {
+        Potential::eval_ad(self, q)
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Kinetic minus potential: L = m q̇² / 2 - V(q)
+// └──────────────────────────────────────────────────────────┘
+/// Adapter running a `Potential1D` through anything that takes a reinla `Lagrangian`
+#[derive(Debug, Copy, Clone)]
+pub struct Mechanical<P: Potential1D> {
+    mass: f64,
+    potential: P,
+}
+
+impl<P: Potential1D> Mechanical<P> {
+    pub fn new(mass: f64, potential: P) -> Self {
+        Self { mass, potential }
+    }
+
+    pub fn get_mass(&self) -> f64 {
+        self.mass
+    }
+
+    pub fn get_potential(&self) -> &P {
+        &self.potential
+    }
+}
+
+impl<P: Potential1D> Lagrangian for Mechanical<P> {
+    type Q = f64;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        0.5 * self.mass * dq.powi(2) - self.potential.eval(*q)
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        let v = self.potential.eval_ad(q[0])?;
+        Some(0.5 * self.mass * dq[0].powi(2) - v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reinla::potential::DoubleWell;
+
+    #[test]
+    fn harmonic_oscillator() {
+        let potential = HarmonicOscillator1D::new(2f64);
+        assert_eq!(potential.eval(0f64), 0f64);
+        assert_eq!(potential.eval(3f64), 18f64);
+    }
+
+    #[test]
+    fn reinla_potential() {
+        let potential = DoubleWell::new(1f64, 1f64);
+        assert_eq!(Potential1D::eval(&potential, 1f64), 0f64);
+        assert_eq!(Potential1D::eval(&potential, 0f64), 1f64);
+    }
+
+    #[test]
+    fn mechanical_lagrangian() {
+        let lagrangian = HarmonicOscillator1D::new(2f64).lagrangian(3f64);
+        assert_eq!(lagrangian.calc(&1f64, &2f64), 6f64 - 2f64);
+        // ∂L/∂q = -ω² q, ∂L/∂q̇ = m q̇
+        assert!((lagrangian.dl_dq(0f64, &1f64, &2f64) + 4f64).abs() < 1e-12);
+        assert!((lagrangian.dl_ddq(0f64, &1f64, &2f64) - 6f64).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::action::{action_1d, Problem};
+use crate::pool::NodePool;
+use itertools::Itertools;
+use reinla::lagrangian::Lagrangian;
+
+/// Stopping criterion of the refinement
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Tolerance {
+    /// |S_l - S_{l-1}| between consecutive levels
+    Action(f64),
+    /// max_k |q_k^l - q_k^{l-1}| between consecutive levels
+    Position(f64),
+}
+
+/// Parameters of the coarse-to-fine solver
+#[derive(Debug, Copy, Clone)]
+pub struct Refinement {
+    /// Number of interior nodes, fixed over all levels
+    pub n: usize,
+    /// Each level divides the spacing by `factor`
+    pub factor: usize,
+    /// Refined candidates cover `width` spacings of the previous level on each side of a node
+    pub width: usize,
+    pub tol: Tolerance,
+    /// Number of refinements after the coarse solve
+    pub max_levels: usize,
+    /// Re-solves at the same spacing per level while a node sits on the edge of its window
+    pub max_recentres: usize,
+}
+
+impl Refinement {
+    pub fn new(n: usize, tol: Tolerance) -> Self {
+        Self {
+            n,
+            factor: 4,
+            width: 1,
+            tol,
+            max_levels: 10,
+            max_recentres: 100,
+        }
+    }
+}
+
+/// Solution at one refinement level
+#[derive(Debug, Clone)]
+pub struct Level {
+    /// Spacing of the candidates at this level
+    pub dq: f64,
+    pub nodes: Vec<f64>,
+    pub action: f64,
+    /// Error estimates w.r.t. the previous level (`None` for the coarse level)
+    pub action_change: Option<f64>,
+    pub position_change: Option<f64>,
+    /// Times the candidate windows were re-centred at this spacing
+    pub recentres: usize,
+    /// Whether a node still sits on the edge of its window, i.e. the optimum may lie outside
+    pub on_boundary: bool,
+}
+
+impl Level {
+    fn converged(&self, tol: Tolerance) -> bool {
+        !self.on_boundary
+            && match tol {
+                Tolerance::Action(tol) => self.action_change.is_some_and(|e| e <= tol),
+                Tolerance::Position(tol) => self.position_change.is_some_and(|e| e <= tol),
+            }
+    }
+}
+
+/// Result of `refine_1d`
+#[derive(Debug, Clone)]
+pub struct Refined {
+    /// Every level, coarse first
+    pub levels: Vec<Level>,
+    /// Whether the last level met `tol`; false if `max_levels` ran out first
+    pub converged: bool,
+}
+
+/// Path of least action with the k-th interior node taken from `layers[k]`
+///
+/// Exact over all combinations by dynamic programming, since the discrete action is a sum of
+/// terms coupling consecutive nodes only; nodes need not be monotone
+pub fn dynamic_programming_1d<L: Lagrangian<Q = f64>>(
+    layers: &[Vec<f64>],
+    problem: &Problem<L>,
+) -> Vec<f64> {
+    let n = layers.len();
+    if n == 0 {
+        return vec![];
+    }
+    let segment = |k: usize, q0: f64, q1: f64| problem.segment_action(n, k, &q0, &q1);
+
+    // best[i]: least action from the start to the i-th candidate of the current layer
+    let mut best = layers[0]
+        .iter()
+        .map(|&q| segment(0, problem.start, q))
+        .collect_vec();
+    let mut parents: Vec<Vec<usize>> = vec![];
+    for k in 1..n {
+        let (next, parent): (Vec<f64>, Vec<usize>) = layers[k]
+            .iter()
+            .map(|&q1| {
+                layers[k - 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &q0)| (best[i] + segment(k, q0, q1), i))
+                    .fold(
+                        (f64::INFINITY, 0),
+                        |acc, x| if x.0 < acc.0 { x } else { acc },
+                    )
+            })
+            .unzip();
+        best = next;
+        parents.push(parent);
+    }
+
+    let (_, mut index) = layers[n - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, &q)| (best[i] + segment(n, q, problem.end), i))
+        .fold(
+            (f64::INFINITY, 0),
+            |acc, x| if x.0 < acc.0 { x } else { acc },
+        );
+
+    let mut nodes = vec![layers[n - 1][index]];
+    for k in (1..n).rev() {
+        index = parents[k - 1][index];
+        nodes.push(layers[k - 1][index]);
+    }
+    nodes.reverse();
+    nodes
+}
+
+/// Coarse-to-fine solver: least action path on `node_pool`, then repeatedly on finer
+/// candidates around every node until `params.tol` is met
+///
+/// The first refinement divides the largest gap of `node_pool`. A node chosen on the edge of
+/// its window re-centres the windows at the same spacing before the spacing shrinks
+pub fn refine_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &Refinement,
+) -> Refined {
+    let layers = vec![node_pool.to_vec(); params.n];
+    let nodes = dynamic_programming_1d(&layers, problem);
+    let mut levels = vec![Level {
+        dq: node_pool.spacing(),
+        action: action_1d(&nodes, problem),
+        nodes,
+        action_change: None,
+        position_change: None,
+        recentres: 0,
+        on_boundary: false,
+    }];
+
+    let steps = (params.factor * params.width) as isize;
+    for _ in 0..params.max_levels {
+        let prev = levels.last().unwrap();
+        let dq = prev.dq / params.factor as f64;
+
+        let mut centres = prev.nodes.clone();
+        let mut recentres = 0;
+        let (nodes, on_boundary) = loop {
+            let layers = centres
+                .iter()
+                .map(|q| (-steps..=steps).map(|j| q + j as f64 * dq).collect_vec())
+                .collect_vec();
+            let nodes = dynamic_programming_1d(&layers, problem);
+            let on_boundary = nodes
+                .iter()
+                .zip(&centres)
+                .any(|(q, c)| ((q - c) / dq).round().abs() as isize == steps);
+            if !on_boundary || recentres == params.max_recentres {
+                break (nodes, on_boundary);
+            }
+            centres = nodes;
+            recentres += 1;
+        };
+
+        let action = action_1d(&nodes, problem);
+        let position_change = nodes
+            .iter()
+            .zip(&prev.nodes)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0f64, f64::max);
+        let level = Level {
+            dq,
+            action_change: Some((action - prev.action).abs()),
+            position_change: Some(position_change),
+            nodes,
+            action,
+            recentres,
+            on_boundary,
+        };
+        let converged = level.converged(params.tol);
+        levels.push(level);
+        if converged {
+            return Refined {
+                levels,
+                converged: true,
+            };
+        }
+    }
+    Refined {
+        levels,
+        converged: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::potential::{HarmonicOscillator1D, Mechanical, Potential1D};
+    use crate::solver::bruteforce_1d;
+    use peroxide::fuga::*;
+    use reinla::lagrangian::one_dim::SHO;
+    use reinla::stationary::StationarySolver;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn dynamic_programming_matches_bruteforce() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::between(0f64, 2f64, 0.1);
+        let dp = dynamic_programming_1d(&vec![pool.to_vec(); 3], &problem);
+        let bf = bruteforce_1d(&pool, 3, &problem, Default::default()).unwrap();
+        assert_eq!(dp, bf);
+    }
+
+    #[test]
+    fn dynamic_programming_allows_non_monotone_paths() {
+        // Thrown up and caught at the same height: q(t) = t (2 - t)
+        let l = reinla::lagrangian::one_dim::UniformGravity::new(1f64, 2f64);
+        let problem = Problem::new(2f64, 0f64, 0f64, &l);
+        let nodes = dynamic_programming_1d(&vec![seq(-1f64, 2f64, 0.25); 3], &problem);
+        assert_eq!(nodes, vec![0.75, 1f64, 0.75]);
+    }
+
+    /// Exact stationary path of the midpoint rule SHO, from solving the discrete
+    /// Euler-Lagrange equations directly
+    fn discrete_sho(problem: &Problem<Mechanical<HarmonicOscillator1D>>, n: usize) -> Vec<f64> {
+        let sho = SHO::new(1f64, 1f64);
+        let mut guess = vec![problem.start; n + 2];
+        guess[n + 1] = problem.end;
+        let path = StationarySolver::new(&sho, problem.dt(n))
+            .solve(guess)
+            .unwrap()
+            .path;
+        path[1..=n].to_vec()
+    }
+
+    #[test]
+    fn refinement_converges_to_discrete_optimum() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 20f64, &l);
+        let params = Refinement::new(15, Tolerance::Position(1e-5));
+        let refined = refine_1d(&NodePool::uniform(0f64, 1f64, 21), &problem, &params);
+
+        assert!(refined.converged);
+        let last = refined.levels.last().unwrap();
+        assert!(!last.on_boundary);
+        assert!(last.position_change.unwrap() <= 1e-5);
+        assert!(refined
+            .levels
+            .windows(2)
+            .all(|w| w[1].action <= w[0].action + 1e-12));
+
+        // The coarse pool is off by up to half a spacing, which re-centring has to walk out of
+        assert!(refined.levels.iter().any(|level| level.recentres > 0));
+        let exact = discrete_sho(&problem, params.n);
+        for (q, q_exact) in last.nodes.iter().zip(&exact) {
+            assert!((q - q_exact).abs() < 1e-5, "{q} vs {q_exact}");
+        }
+        let exact_action = action_1d(&exact, &problem);
+        assert!((last.action - exact_action).abs() < 1e-8);
+    }
+
+    #[test]
+    fn refinement_reports_unmet_tolerance() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 20f64, &l);
+        let mut params = Refinement::new(7, Tolerance::Action(0f64));
+        params.max_levels = 2;
+        let refined = refine_1d(&NodePool::uniform(0f64, 1f64, 21), &problem, &params);
+        assert!(!refined.converged);
+        assert_eq!(refined.levels.len(), 3);
+    }
+
+    #[test]
+    fn refinement_spacing_follows_pool() {
+        let l = HarmonicOscillator1D::new(1f64).lagrangian(1f64);
+        let problem = Problem::new(PI / 2f64, 0f64, 2f64, &l);
+        let pool = NodePool::values(vec![0f64, 0.5, 2f64, 1f64]);
+        let refined = refine_1d(
+            &pool,
+            &problem,
+            &Refinement::new(3, Tolerance::Position(1e-3)),
+        );
+        assert_eq!(refined.levels[0].dq, 1f64);
+        assert_eq!(refined.levels[1].dq, 0.25);
+    }
+}
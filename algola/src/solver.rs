@@ -0,0 +1,712 @@
+use crate::action::{objective_1d, objective_nd, Problem, SearchMode};
+use crate::bruteforce::{bruteforce_1d_streaming, BruteForce};
+use crate::pool::NodePool;
+use itertools::Itertools;
+use rayon::prelude::*;
+use reinla::coordinate::Coordinate;
+use reinla::lagrangian::Lagrangian;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Mutex;
+
+/// Parameters shared by the divide and conquer solvers
+#[derive(Debug, Copy, Clone)]
+pub struct DivideAndConquer {
+    /// Number of halvings; the path gets 2^(depth + 1) - 1 interior nodes
+    pub depth: usize,
+    pub mode: SearchMode,
+}
+
+impl DivideAndConquer {
+    pub fn new(depth: usize, mode: SearchMode) -> Self {
+        Self { depth, mode }
+    }
+
+    fn deeper(&self) -> Self {
+        Self::new(self.depth - 1, self.mode)
+    }
+}
+
+/// Best `n` increasing nodes from `node_pool`, or `None` if every path leaves the domain of L
+pub fn bruteforce_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    n: usize,
+    problem: &Problem<L>,
+    mode: SearchMode,
+) -> Option<Vec<f64>> {
+    let m = node_pool.len();
+    assert!(0 < n && n <= m, "need 0 < n <= {} nodes, got {}", m, n);
+
+    let mut best_action = f64::INFINITY;
+    let mut best_nodes = None;
+    for nodes in node_pool.to_vec().into_iter().combinations(n) {
+        let action = objective_1d(&nodes, problem, mode);
+        if action < best_action {
+            best_action = action;
+            best_nodes = Some(nodes);
+        }
+    }
+    best_nodes
+}
+
+/// Same as `bruteforce_1d`, streamed over all cores with pruning in `SearchMode::Minimal`
+pub fn bruteforce_1d_parallel<L: Lagrangian<Q = f64> + Sync>(
+    node_pool: &NodePool,
+    n: usize,
+    problem: &Problem<L>,
+    mode: SearchMode,
+) -> Option<Vec<f64>> {
+    bruteforce_1d_streaming(node_pool, n, problem, &BruteForce::new(mode)).map(|s| s.nodes)
+}
+
+/// Index in `range` of the best single node of `node_pool` between the ends of `problem`
+fn best_index_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    range: Range<usize>,
+    problem: &Problem<L>,
+    mode: SearchMode,
+) -> usize {
+    let mut best_action = f64::MAX;
+    let mut best_index = range.start;
+    for i in range {
+        let action = objective_1d(&[node_pool.get(i)], problem, mode);
+        if action < best_action {
+            best_action = action;
+            best_index = i;
+        }
+    }
+    best_index
+}
+
+/// Best midpoint first, then each half recursively with half the time
+///
+/// Each half only searches the pool nodes on its side of the midpoint, so paths are monotone;
+/// a half without pool nodes gets the midpoint of its ends
+pub fn divide_and_conquer_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<f64> {
+    dc_range_1d(node_pool, 0..node_pool.len(), problem, params)
+}
+
+/// Recursion of `divide_and_conquer_1d` on `node_pool[range]`
+fn dc_range_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    range: Range<usize>,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<f64> {
+    if range.is_empty() {
+        return vec![(problem.start + problem.end) / 2f64];
+    }
+
+    let index_q = best_index_1d(node_pool, range.clone(), problem, params.mode);
+    let q = node_pool.get(index_q);
+    if params.depth == 0 {
+        return vec![q];
+    }
+
+    let (problem_1, problem_2) = problem.split(q);
+    let best_node_1 = dc_range_1d(
+        node_pool,
+        range.start..index_q,
+        &problem_1,
+        &params.deeper(),
+    );
+    let best_node_2 = dc_range_1d(
+        node_pool,
+        index_q + 1..range.end,
+        &problem_2,
+        &params.deeper(),
+    );
+    best_node_1
+        .into_iter()
+        .chain(vec![q])
+        .chain(best_node_2)
+        .collect()
+}
+
+/// Same as `divide_and_conquer_1d`, but also tries the `depth` pool neighbours of every
+/// midpoint on each side and keeps the best full path
+pub fn divide_and_conquer_and_correct_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<f64> {
+    correct_range_1d(node_pool, 0..node_pool.len(), problem, params, None)
+}
+
+/// Pool indices tried as midpoint by the correcting solvers: the best one first, then its
+/// `width` neighbours on each side within `range`
+fn correction_candidates(index_q: usize, width: usize, range: Range<usize>) -> Vec<usize> {
+    std::iter::once(index_q)
+        .chain(index_q.saturating_sub(width).max(range.start)..index_q)
+        .chain(index_q + 1..(index_q + width + 1).min(range.end))
+        .collect()
+}
+
+/// Recursion of `divide_and_conquer_and_correct_1d` on `node_pool[range]`, memoized when a
+/// memo is given
+fn correct_range_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    range: Range<usize>,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+    mut memo: Option<&mut Memo>,
+) -> Vec<f64> {
+    if range.is_empty() {
+        return vec![(problem.start + problem.end) / 2f64];
+    }
+
+    let key = Subproblem::new(problem, params.depth, range.clone());
+    if let Some(nodes) = memo.as_ref().and_then(|memo| memo.get(&key)) {
+        return nodes.clone();
+    }
+
+    let index_q = best_index_1d(node_pool, range.clone(), problem, params.mode);
+    let nodes = if params.depth == 0 {
+        vec![node_pool.get(index_q)]
+    } else {
+        let mut best_action = f64::MAX;
+        let mut best_node = vec![];
+        for index in correction_candidates(index_q, params.depth, range.clone()) {
+            let q_i = node_pool.get(index);
+            let (problem_1, problem_2) = problem.split(q_i);
+            let range_1 = range.start..index;
+            let range_2 = index + 1..range.end;
+            let path = correct_range_1d(
+                node_pool,
+                range_1,
+                &problem_1,
+                &params.deeper(),
+                memo.as_deref_mut(),
+            )
+            .into_iter()
+            .chain(vec![q_i])
+            .chain(correct_range_1d(
+                node_pool,
+                range_2,
+                &problem_2,
+                &params.deeper(),
+                memo.as_deref_mut(),
+            ))
+            .collect_vec();
+            let action = objective_1d(&path, problem, params.mode);
+            if action < best_action {
+                best_action = action;
+                best_node = path;
+            }
+        }
+        best_node
+    };
+
+    if let Some(memo) = memo {
+        memo.insert(key, nodes.clone());
+    }
+    nodes
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  N-D divide and conquer over a node grid
+// └──────────────────────────────────────────────────────────┘
+/// Every point of the Cartesian product of `axes`, e.g. a 2D grid from two axes
+pub fn node_grid<Q: Coordinate>(axes: &[Vec<f64>]) -> Vec<Q> {
+    axes.iter()
+        .map(|axis| axis.iter().cloned())
+        .multi_cartesian_product()
+        .map(|x| Q::from_slice(&x))
+        .collect()
+}
+
+/// Best single node of `grid` between the ends of `problem`
+pub fn best_midpoint_nd<L: Lagrangian>(
+    grid: &[L::Q],
+    problem: &Problem<L>,
+    mode: SearchMode,
+) -> L::Q {
+    let mut best_action = f64::MAX;
+    let mut best_node = grid[0].clone();
+    for node in grid {
+        let action = objective_nd(std::slice::from_ref(node), problem, mode);
+        if action < best_action {
+            best_action = action;
+            best_node = node.clone();
+        }
+    }
+    best_node
+}
+
+/// Best midpoint from the whole `grid`, then each time half recursively
+///
+/// Unlike `divide_and_conquer_1d` the grid is not split, since N-D nodes have no ordering
+pub fn divide_and_conquer_nd<L: Lagrangian>(
+    grid: &[L::Q],
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<L::Q> {
+    let q = best_midpoint_nd(grid, problem, params.mode);
+    if params.depth == 0 {
+        return vec![q];
+    }
+
+    let (problem_1, problem_2) = problem.split(q.clone());
+    let best_node_1 = divide_and_conquer_nd(grid, &problem_1, &params.deeper());
+    let best_node_2 = divide_and_conquer_nd(grid, &problem_2, &params.deeper());
+    best_node_1
+        .into_iter()
+        .chain(vec![q])
+        .chain(best_node_2)
+        .collect()
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  1D time-halving divide and conquer (full node pool)
+// └──────────────────────────────────────────────────────────┘
+/// Memo key of a subproblem; floats are compared bitwise
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Subproblem {
+    start: u64,
+    end: u64,
+    t: u64,
+    t0: u64,
+    depth: usize,
+    /// Indices of the node pool available to the subproblem
+    range: Range<usize>,
+}
+
+impl Subproblem {
+    fn new<L: Lagrangian<Q = f64>>(
+        problem: &Problem<L>,
+        depth: usize,
+        range: Range<usize>,
+    ) -> Self {
+        Self {
+            start: problem.start.to_bits(),
+            end: problem.end.to_bits(),
+            t: problem.T.to_bits(),
+            t0: problem.t0.to_bits(),
+            depth,
+            range,
+        }
+    }
+}
+
+/// Solved subproblems of the memoized solvers
+type Memo = HashMap<Subproblem, Vec<f64>>;
+
+/// Same as `divide_and_conquer_1d`, but both time halves search the whole `node_pool`
+///
+/// Non-monotone paths (oscillations, a thrown projectile) are reachable this way
+pub fn divide_and_conquer_time_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<f64> {
+    let mut memo = Memo::new();
+    time_halving_1d(node_pool, problem, params, 0, &mut memo)
+}
+
+/// Same as `divide_and_conquer_and_correct_1d`, but both time halves search the whole
+/// `node_pool`
+pub fn divide_and_conquer_and_correct_time_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<f64> {
+    let mut memo = Memo::new();
+    time_halving_1d(node_pool, problem, params, params.depth, &mut memo)
+}
+
+/// Time-halving recursion trying `width` pool neighbours of every midpoint on each side,
+/// memoized on (start, end, T, t0, depth)
+fn time_halving_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+    width: usize,
+    memo: &mut Memo,
+) -> Vec<f64> {
+    let range = 0..node_pool.len();
+    let key = Subproblem::new(problem, params.depth, range.clone());
+    if let Some(nodes) = memo.get(&key) {
+        return nodes.clone();
+    }
+
+    let index_q = best_index_1d(node_pool, range.clone(), problem, params.mode);
+    let nodes = if params.depth == 0 {
+        vec![node_pool.get(index_q)]
+    } else {
+        let mut best_action = f64::MAX;
+        let mut best_node = vec![];
+        for index in correction_candidates(index_q, width, range) {
+            let q_i = node_pool.get(index);
+            let (problem_1, problem_2) = problem.split(q_i);
+            let candidate = time_halving_1d(node_pool, &problem_1, &params.deeper(), width, memo)
+                .into_iter()
+                .chain(vec![q_i])
+                .chain(time_halving_1d(
+                    node_pool,
+                    &problem_2,
+                    &params.deeper(),
+                    width,
+                    memo,
+                ))
+                .collect_vec();
+            let action = objective_1d(&candidate, problem, params.mode);
+            if action < best_action {
+                best_action = action;
+                best_node = candidate;
+            }
+        }
+        best_node
+    };
+
+    memo.insert(key, nodes.clone());
+    nodes
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Memoized and parallel divide and conquer and correct
+// └──────────────────────────────────────────────────────────┘
+/// Same result as `divide_and_conquer_and_correct_1d`, but every subproblem is solved once
+///
+/// Serial, so that `dcc_bench` can tell the gain of memoization from that of parallelism
+pub fn divide_and_conquer_and_correct_memo_1d<L: Lagrangian<Q = f64>>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<f64> {
+    let mut memo = Memo::new();
+    correct_range_1d(
+        node_pool,
+        0..node_pool.len(),
+        problem,
+        params,
+        Some(&mut memo),
+    )
+}
+
+/// Same result as `divide_and_conquer_and_correct_1d`, but every subproblem is solved once
+/// and the candidate midpoints and both halves of each are solved in parallel
+pub fn divide_and_conquer_and_correct_parallel_1d<L: Lagrangian<Q = f64> + Sync>(
+    node_pool: &NodePool,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+) -> Vec<f64> {
+    let memo = Mutex::new(Memo::new());
+    correct_parallel_1d(node_pool, 0..node_pool.len(), problem, params, &memo)
+}
+
+/// Recursion of `divide_and_conquer_and_correct_parallel_1d` on `node_pool[range]`
+fn correct_parallel_1d<L: Lagrangian<Q = f64> + Sync>(
+    node_pool: &NodePool,
+    range: Range<usize>,
+    problem: &Problem<L>,
+    params: &DivideAndConquer,
+    memo: &Mutex<Memo>,
+) -> Vec<f64> {
+    if range.is_empty() {
+        return vec![(problem.start + problem.end) / 2f64];
+    }
+
+    let key = Subproblem::new(problem, params.depth, range.clone());
+    let cached = memo.lock().unwrap().get(&key).cloned();
+    if let Some(nodes) = cached {
+        return nodes;
+    }
+
+    let index_q = best_index_1d(node_pool, range.clone(), problem, params.mode);
+    let nodes = if params.depth == 0 {
+        vec![node_pool.get(index_q)]
+    } else {
+        let paths = correction_candidates(index_q, params.depth, range.clone())
+            .into_par_iter()
+            .map(|index| {
+                let q_i = node_pool.get(index);
+                let (problem_1, problem_2) = problem.split(q_i);
+                let (best_node_1, best_node_2) = rayon::join(
+                    || {
+                        let range_1 = range.start..index;
+                        correct_parallel_1d(node_pool, range_1, &problem_1, &params.deeper(), memo)
+                    },
+                    || {
+                        let range_2 = index + 1..range.end;
+                        correct_parallel_1d(node_pool, range_2, &problem_2, &params.deeper(), memo)
+                    },
+                );
+                let path = best_node_1
+                    .into_iter()
+                    .chain(vec![q_i])
+                    .chain(best_node_2)
+                    .collect_vec();
+                (objective_1d(&path, problem, params.mode), path)
+            })
+            .collect::<Vec<_>>();
+
+        // Ties go to the earlier candidate, as in the serial solver
+        let mut best_action = f64::MAX;
+        let mut best_node = vec![];
+        for (action, path) in paths {
+            if action < best_action {
+                best_action = action;
+                best_node = path;
+            }
+        }
+        best_node
+    };
+
+    memo.lock().unwrap().insert(key, nodes.clone());
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::action_1d;
+    use crate::potential::{HarmonicOscillator1D, Mechanical, Potential1D};
+    use peroxide::fuga::*;
+    use reinla::lagrangian::one_dim::{FreeBody, UniformGravity, SHO};
+    use reinla::lagrangian::two_dim::{self, CentralForce, CentralPotential};
+    use std::f64::consts::PI;
+
+    const DQ: f64 = 0.1;
+
+    fn node_pool(start: f64, end: f64) -> NodePool {
+        NodePool::between(start, end, DQ)
+    }
+
+    fn free(l: &FreeBody) -> Problem<'_, FreeBody> {
+        Problem::new(1f64, 0f64, 2f64, l)
+    }
+
+    fn sho(l: &Mechanical<HarmonicOscillator1D>) -> Problem<'_, Mechanical<HarmonicOscillator1D>> {
+        Problem::new(PI / 2f64, 0f64, 2f64, l)
+    }
+
+    fn sho_lagrangian() -> Mechanical<HarmonicOscillator1D> {
+        HarmonicOscillator1D::new(1f64).lagrangian(1f64)
+    }
+
+    fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() <= tol, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn bruteforce_free_particle_is_straight() {
+        let l = FreeBody::new(1f64);
+        let nodes =
+            bruteforce_1d(&node_pool(0f64, 2f64), 3, &free(&l), SearchMode::Minimal).unwrap();
+        assert_close(&nodes, &[0.5, 1.0, 1.5], 1e-12);
+    }
+
+    #[test]
+    fn bruteforce_parallel_matches_serial() {
+        let l = sho_lagrangian();
+        let pool = node_pool(0f64, 2f64);
+        let serial = bruteforce_1d(&pool, 3, &sho(&l), SearchMode::Minimal);
+        let parallel = bruteforce_1d_parallel(&pool, 3, &sho(&l), SearchMode::Minimal);
+        assert!(serial.is_some());
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn bruteforce_stationary_matches_minimal_below_conjugate_point() {
+        let l = sho_lagrangian();
+        let pool = node_pool(0f64, 2f64);
+        let minimal = bruteforce_1d(&pool, 3, &sho(&l), SearchMode::Minimal).unwrap();
+        let stationary = bruteforce_1d(&pool, 3, &sho(&l), SearchMode::Stationary).unwrap();
+        assert_close(&minimal, &stationary, DQ + 1e-12);
+    }
+
+    #[test]
+    fn potential_adapter_matches_reinla_lagrangian() {
+        // m q̇² / 2 - ω² q² / 2 with m = 3, ω = 2 is reinla's SHO with k = 4
+        let adapter = HarmonicOscillator1D::new(2f64).lagrangian(3f64);
+        let reinla_sho = SHO::new(3f64, 4f64);
+        let pool = node_pool(0f64, 2f64);
+        let a = bruteforce_1d(
+            &pool,
+            3,
+            &Problem::new(1f64, 0f64, 2f64, &adapter),
+            SearchMode::Minimal,
+        );
+        let b = bruteforce_1d(
+            &pool,
+            3,
+            &Problem::new(1f64, 0f64, 2f64, &reinla_sho),
+            SearchMode::Minimal,
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn divide_and_conquer_free_particle_is_straight() {
+        let l = FreeBody::new(1f64);
+        let params = DivideAndConquer::new(1, SearchMode::Minimal);
+        let nodes = divide_and_conquer_1d(&node_pool(0f64, 2f64), &free(&l), &params);
+        assert_close(&nodes, &[0.5, 1.0, 1.5], 1e-12);
+    }
+
+    #[test]
+    fn divide_and_conquer_tracks_harmonic_oscillator() {
+        // q(t) = 2 sin(t) on [0, π/2]
+        let l = sho_lagrangian();
+        let params = DivideAndConquer::new(1, SearchMode::Minimal);
+        let nodes = divide_and_conquer_1d(&node_pool(0f64, 2f64), &sho(&l), &params);
+        let exact = (1..=3)
+            .map(|k| 2f64 * (k as f64 * PI / 8f64).sin())
+            .collect_vec();
+        assert_close(&nodes, &exact, 0.1);
+    }
+
+    #[test]
+    fn correction_never_worse_than_divide_and_conquer() {
+        let l = sho_lagrangian();
+        let pool = node_pool(0f64, 2f64);
+        let params = DivideAndConquer::new(2, SearchMode::Minimal);
+        let dc = divide_and_conquer_1d(&pool, &sho(&l), &params);
+        let dcc = divide_and_conquer_and_correct_1d(&pool, &sho(&l), &params);
+        assert_eq!(dcc.len(), dc.len());
+        assert!(action_1d(&dcc, &sho(&l)) <= action_1d(&dc, &sho(&l)) + 1e-12);
+    }
+
+    #[test]
+    fn node_grid_covers_product() {
+        let grid: Vec<(f64, f64)> = node_grid(&[vec![0f64, 1f64], vec![2f64, 3f64, 4f64]]);
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[0], (0f64, 2f64));
+        assert_eq!(grid[5], (1f64, 4f64));
+    }
+
+    #[test]
+    fn divide_and_conquer_nd_matches_1d() {
+        let l = sho_lagrangian();
+        let pool = node_pool(0f64, 2f64);
+        let params = DivideAndConquer::new(1, SearchMode::Minimal);
+        let nodes_1d = divide_and_conquer_1d(&pool, &sho(&l), &params);
+        let nodes_nd = divide_and_conquer_nd(&pool.to_vec(), &sho(&l), &params);
+        assert_eq!(nodes_1d, nodes_nd);
+    }
+
+    #[test]
+    fn divide_and_conquer_2d_free_particle_is_straight() {
+        let l = two_dim::FreeBody::new(1f64);
+        let axis = seq(0f64, 2f64, 0.25);
+        let grid = node_grid(&[axis.clone(), axis]);
+        let problem = Problem::new(1f64, (0f64, 0f64), (2f64, 1f64), &l);
+        let params = DivideAndConquer::new(1, SearchMode::Minimal);
+        let nodes = divide_and_conquer_nd(&grid, &problem, &params);
+        assert_eq!(nodes, vec![(0.5, 0.25), (1f64, 0.5), (1.5, 0.75)]);
+    }
+
+    #[test]
+    fn divide_and_conquer_2d_tracks_isotropic_oscillator() {
+        // q(t) = (2 sin t, cos t) on [0, π/2]
+        let l = CentralForce::new(1f64, CentralPotential::Harmonic { k: 1f64 });
+        let grid = node_grid(&[seq(-0.5, 2.5, DQ), seq(-0.5, 1.5, DQ)]);
+        let problem = Problem::new(PI / 2f64, (0f64, 1f64), (2f64, 0f64), &l);
+        let params = DivideAndConquer::new(1, SearchMode::Minimal);
+        let nodes = divide_and_conquer_nd(&grid, &problem, &params);
+        for (k, (x, y)) in nodes.into_iter().enumerate() {
+            let t = (k + 1) as f64 * PI / 8f64;
+            assert!((x - 2f64 * t.sin()).abs() <= 0.1);
+            assert!((y - t.cos()).abs() <= 0.1);
+        }
+    }
+
+    fn max_error<F: Fn(f64) -> f64>(nodes: &[f64], dt: f64, exact: F) -> f64 {
+        nodes
+            .iter()
+            .enumerate()
+            .map(|(k, q)| (q - exact((k + 1) as f64 * dt)).abs())
+            .fold(0f64, f64::max)
+    }
+
+    #[test]
+    fn time_halving_matches_nd() {
+        let l = sho_lagrangian();
+        let pool = node_pool(-1f64, 2f64);
+        let params = DivideAndConquer::new(2, SearchMode::Minimal);
+        let nodes_time = divide_and_conquer_time_1d(&pool, &sho(&l), &params);
+        let nodes_nd = divide_and_conquer_nd(&pool.to_vec(), &sho(&l), &params);
+        assert_eq!(nodes_time, nodes_nd);
+    }
+
+    #[test]
+    fn time_halving_follows_non_monotone_oscillation() {
+        // q(t) = sin(t) / (2 sin T) overshoots the end point for T = 0.9π
+        let l = sho_lagrangian();
+        let problem = Problem::new(0.9 * PI, 0f64, 0.5, &l);
+        let pool = node_pool(-2f64, 2f64);
+        let params = DivideAndConquer::new(2, SearchMode::Minimal);
+
+        let time = divide_and_conquer_time_1d(&pool, &problem, &params);
+        let corrected = divide_and_conquer_and_correct_time_1d(&pool, &problem, &params);
+        assert!(time.iter().any(|&q| q > 0.5 + DQ));
+        assert!(time[6] > 0.5);
+        assert!(action_1d(&corrected, &problem) <= action_1d(&time, &problem) + 1e-12);
+    }
+
+    #[test]
+    fn time_halving_throws_projectile() {
+        // q(t) = g t (T - t) / 2 with q(0) = q(T) = 0
+        let l = UniformGravity::new(1f64, 2f64);
+        let problem = Problem::new(2f64, 0f64, 0f64, &l);
+        let pool = node_pool(-2f64, 2f64);
+        let params = DivideAndConquer::new(1, SearchMode::Minimal);
+        let exact = |s: f64| s * (2f64 - s);
+
+        let monotone = divide_and_conquer_1d(&pool, &problem, &params);
+        let time = divide_and_conquer_time_1d(&pool, &problem, &params);
+        let corrected = divide_and_conquer_and_correct_time_1d(&pool, &problem, &params);
+        assert!(max_error(&time, 0.5, exact) <= DQ + 1e-9);
+        assert!(max_error(&corrected, 0.5, exact) <= DQ + 1e-9);
+        assert!(max_error(&monotone, 0.5, exact) > 0.3);
+    }
+
+    #[test]
+    fn parallel_correction_matches_serial() {
+        let l = sho_lagrangian();
+        let pool = node_pool(0f64, 2f64);
+        for depth in 0..4 {
+            let params = DivideAndConquer::new(depth, SearchMode::Minimal);
+            let serial = divide_and_conquer_and_correct_1d(&pool, &sho(&l), &params);
+            let memo = divide_and_conquer_and_correct_memo_1d(&pool, &sho(&l), &params);
+            let parallel = divide_and_conquer_and_correct_parallel_1d(&pool, &sho(&l), &params);
+            assert_eq!(serial, memo);
+            assert_eq!(serial, parallel);
+        }
+    }
+
+    #[test]
+    fn solvers_accept_any_spacing() {
+        let l = sho_lagrangian();
+        let params = DivideAndConquer::new(2, SearchMode::Minimal);
+        for dq in [0.01, 1f64 / 3f64, 0.07] {
+            let pool = NodePool::between(0f64, 2f64, dq);
+            let dc = divide_and_conquer_1d(&pool, &sho(&l), &params);
+            let dcc = divide_and_conquer_and_correct_1d(&pool, &sho(&l), &params);
+            let parallel = divide_and_conquer_and_correct_parallel_1d(&pool, &sho(&l), &params);
+            assert_eq!(dc.len(), 7);
+            assert_eq!(dcc, parallel);
+            assert!(action_1d(&dcc, &sho(&l)) <= action_1d(&dc, &sho(&l)) + 1e-12);
+        }
+    }
+
+    #[test]
+    fn solvers_accept_non_uniform_pool() {
+        // Denser near the end point, where q(t) = 2 sin(t) flattens out
+        let l = sho_lagrangian();
+        let pool = NodePool::values((1..40).map(|i| 2f64 * (i as f64 / 40f64).sqrt()).collect());
+        let params = DivideAndConquer::new(1, SearchMode::Minimal);
+        let nodes = divide_and_conquer_and_correct_1d(&pool, &sho(&l), &params);
+        let exact = (1..=3)
+            .map(|k| 2f64 * (k as f64 * PI / 8f64).sin())
+            .collect_vec();
+        assert_close(&nodes, &exact, 0.1);
+    }
+}
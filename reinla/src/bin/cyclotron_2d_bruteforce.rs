@@ -0,0 +1,59 @@
+use reinla::discrete::DiscreteLagrangian;
+use reinla::lagrangian::em::{ChargedParticle, ScalarPotential, VectorPotential};
+use reinla::lattice::n_dim::LatticeND;
+use reinla::lattice::Axis;
+use reinla::shooting::Shooting;
+
+const M: f64 = 1.0;
+const E: f64 = 1.0;
+const B: f64 = 0.5;
+const T: usize = 4;
+
+fn lattice(vector: VectorPotential) -> LatticeND<ChargedParticle<(f64, f64)>> {
+    // x ∈ [-0.5, 2.5], y ∈ [-1, 1] with spacing 0.25; from (0, 0) to (2, 0.5)
+    let mut env = LatticeND::new(
+        vec![2, 4],
+        vec![10, 6],
+        vec![(0, 12), (0, 8)],
+        T,
+        ChargedParticle::new(M, E, ScalarPotential::Zero, vector),
+    );
+    env.set_axes(vec![Axis::linear(-0.5, 0.25), Axis::linear(-1.0, 0.25)]);
+    env
+}
+
+fn main() {
+    let symmetric = lattice(VectorPotential::UniformSymmetric { b: B });
+    let landau = lattice(VectorPotential::UniformLandau { b: B });
+
+    let path_s = symmetric.brute_force().unwrap();
+    let path_l = landau.brute_force().unwrap();
+    let q = symmetric.positions(&path_s);
+    println!("Symmetric gauge path: {:?}", q);
+    println!("Landau gauge path: {:?}", landau.positions(&path_l));
+
+    // A_Landau - A_symmetric = ∇χ with χ = -B x y / 2
+    let chi = |(x, y): (f64, f64)| -0.5 * B * x * y;
+    let (start, end) = (q[0], q[T]);
+    println!(
+        "Action difference: {:.6} (expected e Δχ = {:.6})",
+        landau.action(&path_s) - symmetric.action(&path_s),
+        E * (chi(end) - chi(start))
+    );
+
+    // Cyclotron arc with ω = e B / m through the same end points
+    let shooting = Shooting::new(symmetric.lagrangian(), T as f64);
+    let arc = shooting.solve(&start, &end, T - 1).unwrap();
+    println!("Cyclotron arc: {:?}", arc.q);
+    println!(
+        "Action (lattice / arc): {:.6} / {:.6}",
+        symmetric.action(&path_s),
+        DiscreteLagrangian::new(symmetric.lagrangian(), 1f64).action(&arc.q)
+    );
+
+    let deviation = q
+        .iter()
+        .zip(arc.q.iter())
+        .fold(0f64, |acc, (a, b)| acc.max((a.0 - b.0).hypot(a.1 - b.1)));
+    println!("Lattice vs cyclotron arc: {:.4e}", deviation);
+}
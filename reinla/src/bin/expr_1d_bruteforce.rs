@@ -0,0 +1,62 @@
+use reinla::lagrangian::expr::{parse_params, ExprLagrangian};
+use reinla::lattice::one_dim::Lattice1D;
+use std::env::args;
+use std::process::exit;
+
+const RESIDUAL_TOL: f64 = 1.0;
+
+fn usage(program: &str) -> ! {
+    eprintln!(
+        "usage: {} \"<lagrangian>\" \"<name=value,...>\" <N> <T>",
+        program
+    );
+    exit(1);
+}
+
+/// Positive integer argument, or exit with an error naming it
+fn positive(arg: &str, name: &str) -> usize {
+    match arg.parse::<usize>() {
+        Ok(x) if x >= 1 => x,
+        _ => {
+            eprintln!(
+                "error: {} must be a positive integer, got \"{}\"",
+                name, arg
+            );
+            exit(1);
+        }
+    }
+}
+
+/// Usage: expr_1d_bruteforce "<lagrangian>" "<name=value,...>" <N> <T>
+///
+/// e.g. expr_1d_bruteforce "0.5*m*dq^2 - m*g*q" "m=1,g=2" 10 4
+#[allow(non_snake_case)]
+pub fn main() {
+    let args = args().collect::<Vec<String>>();
+    if args.len() != 5 {
+        usage(&args[0]);
+    }
+    let params = parse_params(&args[2]).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        exit(1);
+    });
+    let params = params
+        .iter()
+        .map(|(name, x)| (name.as_str(), *x))
+        .collect::<Vec<_>>();
+    let lagrangian = ExprLagrangian::parse(&args[1], &params).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        exit(1);
+    });
+    let N = positive(&args[3], "N");
+    let m = positive(&args[4], "T");
+
+    let env = Lattice1D::new(N + 1, 0, N as i64, m, lagrangian);
+    let result = env.brute_force().unwrap_or_else(|| {
+        eprintln!("error: no admissible path with N = {} and T = {}", N, m);
+        exit(1);
+    });
+    println!("L = {}", args[1]);
+    println!("{:?}", result);
+    println!("{:?}", env.verify(&result, RESIDUAL_TOL));
+}
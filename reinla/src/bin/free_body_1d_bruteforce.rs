@@ -3,6 +3,8 @@ use reinla::lattice::one_dim::Lattice1D;
 use std::env::args;
 
 const M: f64 = 1.0;
+const RESIDUAL_TOL: f64 = 1.0;
+const DEVIATION_TOL: f64 = 1.0;
 
 #[allow(non_snake_case)]
 pub fn main() {
@@ -10,6 +12,10 @@ pub fn main() {
     let N = args[1].parse::<usize>().unwrap();
     let m = args[2].parse::<usize>().unwrap();
     let env = Lattice1D::new(N+1, 0, N as i64, m, FreeBody::new(M));
-    let result = env.brute_force();
+    let result = env.brute_force().unwrap();
     println!("{:?}", result);
+
+    let verdict =
+        env.verify_with_solution(&result, RESIDUAL_TOL, DEVIATION_TOL, |t| N as f64 * t / m as f64);
+    println!("{:?}", verdict);
 }
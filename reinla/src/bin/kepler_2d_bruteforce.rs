@@ -0,0 +1,49 @@
+use reinla::discrete::DiscreteLagrangian;
+use reinla::lagrangian::two_dim::{CentralForcePolar, CentralPotential};
+use reinla::lattice::n_dim::LatticeND;
+use reinla::lattice::Axis;
+use reinla::shooting::Shooting;
+
+const M: f64 = 1.0;
+const K: f64 = 1.0;
+const N_R: i64 = 16;
+const N_THETA: usize = 24;
+const T: usize = 3;
+
+fn main() {
+    let potential = CentralPotential::Kepler { k: K };
+    let axes = vec![Axis::linear(0.5, 0.1), Axis::angular(N_THETA)];
+    let theta = N_THETA as i64 / 2; // θ = 0
+
+    // From (r, θ) = (1, 0) to (1, π/2)
+    let mut env = LatticeND::new(
+        vec![5, theta],
+        vec![5, theta + N_THETA as i64 / 4],
+        vec![(0, N_R - 1), (0, N_THETA as i64 - 1)],
+        T,
+        CentralForcePolar::new(M, potential),
+    );
+    env.set_axes(axes);
+
+    let result = env.brute_force().unwrap();
+    let q = env.positions(&result);
+    println!("Lattice path (r, θ): {:?}", q);
+    println!("Lattice action: {:.6}", env.action(&result));
+
+    // Keplerian arc through the same end points
+    let start = q[0];
+    let end = q[T];
+    let shooting = Shooting::new(env.lagrangian(), T as f64);
+    let arc = shooting.solve(&start, &end, T - 1).unwrap();
+    let discrete = DiscreteLagrangian::new(env.lagrangian(), 1f64);
+    println!("Kepler arc (r, θ): {:?}", arc.q);
+    println!("Kepler arc action: {:.6}", discrete.action(&arc.q));
+
+    let to_xy = |(r, th): (f64, f64)| (r * th.cos(), r * th.sin());
+    let deviation = q.iter().zip(arc.q.iter()).fold(0f64, |acc, (&a, &b)| {
+        let (ax, ay) = to_xy(a);
+        let (bx, by) = to_xy(b);
+        acc.max((ax - bx).hypot(ay - by))
+    });
+    println!("Lattice vs Kepler arc: {:.4e}", deviation);
+}
@@ -0,0 +1,33 @@
+use reinla::hamiltonian::{conservation, Symmetry};
+use reinla::integrator::VariationalIntegrator;
+use reinla::lagrangian::two_dim::{CentralForce, CentralPotential};
+
+const M: f64 = 1.0;
+const K: f64 = 1.0;
+const DT: f64 = 0.01;
+const STEPS: usize = 10000;
+
+fn main() {
+    let kepler = CentralForce::new(M, CentralPotential::Kepler { k: K });
+    let integrator = VariationalIntegrator::new(&kepler, DT);
+
+    // Eccentric orbit starting at perihelion (1, 0) with speed 1.2
+    let path = integrator
+        .integrate((1f64, 0f64), (1f64, 1.2 * DT), STEPS)
+        .unwrap();
+
+    for (name, symmetry) in [
+        ("Energy", Symmetry::TimeTranslation),
+        ("Angular momentum", Symmetry::rotation_2d()),
+        (
+            "Momentum p_x (not a symmetry)",
+            Symmetry::Translation(vec![1f64, 0f64]),
+        ),
+    ] {
+        let c = conservation(&kepler, &symmetry, &path, DT).unwrap();
+        println!(
+            "{}: Q0 = {:.6}\tmax drift = {:.4e}\trelative = {:.4e}",
+            name, c.values[0], c.max_drift, c.relative_drift
+        );
+    }
+}
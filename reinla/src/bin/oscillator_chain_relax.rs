@@ -0,0 +1,63 @@
+use reinla::lagrangian::field::{Boundary, OscillatorChain, ScalarField};
+use reinla::lattice::field::FieldLattice;
+use reinla::lattice::Axis;
+use std::f64::consts::PI;
+
+const M: f64 = 1.0;
+const K: f64 = 1.0;
+const SITES: usize = 8;
+const T: usize = 8;
+const DT: f64 = 0.15;
+const A: f64 = 1.0;
+const MAX_SWEEPS: usize = 100;
+
+fn main() {
+    // Lowest normal mode of a chain with fixed ends: q_i(t) = A sin(i π / (n + 1)) cos(ω t)
+    let chain = OscillatorChain::new(M, K, Boundary::Fixed);
+    let omega = chain.normal_mode(SITES, 1);
+    let shape = (1..=SITES)
+        .map(|i| A * (i as f64 * PI / (SITES + 1) as f64).sin())
+        .collect::<Vec<f64>>();
+    let at = |t: f64| {
+        shape
+            .iter()
+            .map(|s| s * (omega * t).cos())
+            .collect::<Vec<f64>>()
+    };
+
+    let mut env = FieldLattice::new(at(0f64), at(T as f64 * DT), (0, 60), T, chain);
+    env.set_axis(Axis::linear(-1.5, 0.05));
+    env.set_dt(DT);
+
+    let (config, sweeps) = env.relax(env.linear(), MAX_SWEEPS);
+    let q = env.positions(&config);
+    let deviation = q.iter().enumerate().fold(0f64, |acc, (k, slice)| {
+        at(k as f64 * DT)
+            .iter()
+            .zip(slice.iter())
+            .fold(acc, |acc, (a, b)| acc.max((a - b).abs()))
+    });
+    println!(
+        "Chain: {} sweeps, action {:.6}",
+        sweeps,
+        env.action(&config)
+    );
+    println!("Chain vs normal mode: {:.4e}", deviation);
+
+    // Same boundary data for a φ⁴ field
+    let field = ScalarField::phi4(1.0, 0.5, 1.0, Boundary::Fixed);
+    let mut env = FieldLattice::new(at(0f64), at(T as f64 * DT), (0, 60), T, field);
+    env.set_axis(Axis::linear(-1.5, 0.05));
+    env.set_dt(DT);
+
+    let init = env.linear();
+    let action_init = env.action(&init);
+    let (config, sweeps) = env.relax(init, MAX_SWEEPS);
+    println!(
+        "φ⁴: {} sweeps, action {:.6} -> {:.6}",
+        sweeps,
+        action_init,
+        env.action(&config)
+    );
+    println!("{:?}", env.positions(&config)[T / 2]);
+}
@@ -0,0 +1,50 @@
+use reinla::discrete::DiscreteLagrangian;
+use reinla::lagrangian::one_dim::Pendulum;
+use reinla::lattice::n_dim::LatticeND;
+use reinla::lattice::Axis;
+use reinla::shooting::Shooting;
+use std::f64::consts::PI;
+
+const M: f64 = 1.0;
+const L: f64 = 1.0;
+const G: f64 = 0.1;
+const N: usize = 36;
+const T: usize = 5;
+
+fn main() {
+    let axis = Axis::angular(N);
+    let init = N as i64 / 2; // θ = 0
+    let end = 3 * N as i64 / 4; // θ = π/2
+
+    let mut env = LatticeND::new(
+        vec![init],
+        vec![end],
+        vec![(0, N as i64 - 1)],
+        T,
+        Pendulum::new(M, L, G),
+    );
+    env.set_axes(vec![axis]);
+
+    let result = env.brute_force().unwrap();
+    let q = env.positions(&result);
+    println!("Lattice path: {:?}", q);
+    println!("Lattice action: {:.6}", env.action(&result));
+    println!("{:?}", env.verify(&result, axis.spacing()));
+
+    // Stationary paths that reach θ = π/2 directly or after one extra turn
+    let q0 = axis.position(init);
+    let q1 = axis.position(end);
+    let discrete = DiscreteLagrangian::new(env.lagrangian(), 1f64);
+    for (name, q_end) in [("direct", q1), ("winding", q1 - 2f64 * PI)] {
+        let shooting = Shooting::new(env.lagrangian(), T as f64);
+        match shooting.solve(&q0, &q_end, T - 1) {
+            Some(trajectory) => println!(
+                "{}: {:?}\taction: {:.6}",
+                name,
+                trajectory.q,
+                discrete.action(&trajectory.q)
+            ),
+            None => println!("{}: shooting did not converge", name),
+        }
+    }
+}
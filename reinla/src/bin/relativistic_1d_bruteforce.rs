@@ -0,0 +1,64 @@
+use reinla::lagrangian::one_dim::{RelativisticParticle, UniformGravity};
+use reinla::lagrangian::Lagrangian;
+use reinla::lattice::one_dim::Lattice1D;
+use reinla::lattice::Axis;
+
+const M: f64 = 1.0;
+const C: f64 = 1.0;
+const G: f64 = 1.0;
+const N: usize = 40;
+const T: usize = 8;
+const DX: f64 = 0.025;
+const DT: f64 = 0.2;
+const RESIDUAL_TOL: f64 = 0.5;
+const DEVIATION_TOL: f64 = 2.0 * DX;
+
+fn lattice<L: Lagrangian<Q = f64>>(lagrangian: L) -> Lattice1D<L> {
+    let mut env = Lattice1D::new(N + 1, 0, N as i64, T, lagrangian);
+    env.set_axis(Axis::linear(0f64, DX));
+    env.set_dt(DT);
+    env
+}
+
+fn max_speed(q: &[f64]) -> f64 {
+    q.windows(2)
+        .fold(0f64, |acc, w| acc.max((w[1] - w[0]).abs() / DT))
+}
+
+fn main() {
+    let relativistic = lattice(RelativisticParticle::with_potential(M, C, |q: &f64| {
+        M * G * q
+    }));
+    let newtonian = lattice(UniformGravity::new(M, G));
+
+    let result_rel = relativistic.brute_force().unwrap();
+    let q = relativistic.positions(&result_rel);
+    println!("Relativistic path: {:?}", q);
+    println!("Relativistic max speed: {:.4} c", max_speed(&q) / C);
+
+    let result = newtonian.brute_force().unwrap();
+    let q_newton = newtonian.positions(&result);
+    println!("Newtonian path: {:?}", q_newton);
+    println!("Newtonian max speed: {:.4} c", max_speed(&q_newton) / C);
+
+    // Exact motion under constant force: x(t) = (E(p0) - E(p0 - m g t)) / (m g)
+    let energy = |p: f64| C * (M.powi(2) * C.powi(2) + p.powi(2)).sqrt();
+    let t_end = T as f64 * DT;
+    let x_end = N as f64 * DX;
+    let (mut lo, mut hi) = (0f64, 100f64);
+    for _ in 0..100 {
+        let p0 = 0.5 * (lo + hi);
+        if energy(p0) - energy(p0 - M * G * t_end) < M * G * x_end {
+            lo = p0;
+        } else {
+            hi = p0;
+        }
+    }
+    let p0 = 0.5 * (lo + hi);
+    let exact = |t: f64| (energy(p0) - energy(p0 - M * G * t)) / (M * G);
+    println!("Exact initial speed: {:.4} c", p0 * C / energy(p0));
+    println!(
+        "{:?}",
+        relativistic.verify_with_solution(&result_rel, RESIDUAL_TOL, DEVIATION_TOL, exact)
+    );
+}
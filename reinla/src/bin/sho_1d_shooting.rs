@@ -0,0 +1,33 @@
+use reinla::lagrangian::one_dim::SHO;
+use reinla::shooting::Shooting;
+use std::f64::consts::PI;
+
+const M: f64 = 1.0;
+const K: f64 = 1.0;
+
+#[allow(non_snake_case)]
+fn main() {
+    let A = 0f64;
+    let B = 20f64;
+    let T = 2f64 * PI / 3f64;
+    let N = 7;
+    let omega = (K / M).sqrt();
+
+    let sho = SHO::new(M, K);
+    let shooting = Shooting::new(&sho, T);
+    let trajectory = shooting.solve(&A, &B, N).unwrap();
+
+    let error = trajectory
+        .t
+        .iter()
+        .zip(trajectory.q.iter())
+        .fold(0f64, |acc, (t, q)| {
+            let q_true = A * (omega * t).cos()
+                + (B - A * (omega * T).cos()) / (omega * T).sin() * (omega * t).sin();
+            acc.max((q - q_true).abs())
+        });
+
+    println!("v0: {:.6}", trajectory.v0);
+    println!("q: {:?}", trajectory.q);
+    println!("max error: {:.4e}", error);
+}
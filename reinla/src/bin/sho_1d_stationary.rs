@@ -0,0 +1,53 @@
+use reinla::lagrangian::one_dim::SHO;
+use reinla::lattice::n_dim::LatticeND;
+use reinla::lattice::{Axis, SearchMode};
+use reinla::stationary::StationarySolver;
+
+const M: f64 = 1.0;
+const K: f64 = 1.0;
+const T: usize = 5;
+const DT: f64 = 0.75;
+
+/// SHO from q = 0 to q = 1 with ωT = 3.75 > π: the true path is a saddle of the action
+fn main() {
+    let omega = (K / M).sqrt();
+    let t_end = T as f64 * DT;
+    let exact = |t: f64| (omega * t).sin() / (omega * t_end).sin();
+
+    // q ∈ [-3, 1] with spacing 0.25
+    let mut env = LatticeND::new(vec![12], vec![16], vec![(0, 16)], T, SHO::new(M, K));
+    env.set_axes(vec![Axis::linear(-3.0, 0.25)]);
+    env.set_dt(DT);
+
+    let sho = SHO::new(M, K);
+    let solver = StationarySolver::new(&sho, DT);
+    let mut guess = vec![];
+    for mode in [SearchMode::Minimal, SearchMode::Stationary] {
+        env.set_search_mode(mode);
+        let path = env.brute_force().unwrap();
+        let q = env.positions(&path);
+        guess = q.clone();
+        let deviation = q.iter().enumerate().fold(0f64, |acc, (k, x)| {
+            acc.max((x - exact(k as f64 * DT)).abs())
+        });
+        println!("{:?}: {:?}", mode, q);
+        println!(
+            "\taction: {:.6}\tresidual²: {:.4e}\tdeviation: {:.4e}",
+            env.action(&path),
+            solver.residual_sq(&q),
+            deviation
+        );
+    }
+
+    // Newton from the stationary lattice path
+    let stationary = solver.solve(guess).unwrap();
+    println!("Newton: {:?}", stationary.path);
+    println!(
+        "\taction: {:.6}\tmax residual: {:.4e}\titerations: {}",
+        stationary.action, stationary.max_residual, stationary.iterations
+    );
+    println!(
+        "\tMorse index: {}\teigenvalues: {:?}",
+        stationary.morse_index, stationary.eigenvalues
+    );
+}
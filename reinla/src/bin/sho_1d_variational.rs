@@ -0,0 +1,25 @@
+use reinla::integrator::VariationalIntegrator;
+use reinla::lagrangian::one_dim::SHO;
+
+const M: f64 = 1.0;
+const K: f64 = 1.0;
+const DT: f64 = 0.1;
+const STEPS: usize = 100000;
+
+fn main() {
+    let sho = SHO::new(M, K);
+    let integrator = VariationalIntegrator::new(&sho, DT);
+
+    let omega = (K / M).sqrt();
+    let path = integrator
+        .integrate(1f64, (omega * DT).cos(), STEPS)
+        .unwrap();
+    let energy = integrator.energy(&path);
+
+    let e0 = energy[0];
+    let drift = energy
+        .iter()
+        .fold(0f64, |acc, e| acc.max((e - e0).abs() / e0.abs()));
+
+    println!("E0: {:.6}\tmax relative drift: {:.4e}", e0, drift);
+}
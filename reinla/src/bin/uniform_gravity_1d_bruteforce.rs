@@ -7,6 +7,8 @@ type L = UniformGravity;
 
 const M: f64 = 1.0;
 const G: f64 = 2.0;
+const RESIDUAL_TOL: f64 = 1.0;
+const DEVIATION_TOL: f64 = 1.0;
 
 #[allow(non_snake_case)]
 pub fn main() {
@@ -14,6 +16,12 @@ pub fn main() {
     let N = args[1].parse::<usize>().unwrap();
     let m = args[2].parse::<usize>().unwrap();
     let env = E::new(N+1, 0, N as i64, m, L::new(M, G));
-    let result = env.brute_force();
+    let result = env.brute_force().unwrap();
     println!("{:?}", result);
+
+    let T = m as f64;
+    let verdict = env.verify_with_solution(&result, RESIDUAL_TOL, DEVIATION_TOL, |t| {
+        N as f64 * t / T + 0.5 * G * t * (T - t)
+    });
+    println!("{:?}", verdict);
 }
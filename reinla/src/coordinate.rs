@@ -0,0 +1,108 @@
+/// Generalized coordinate that can be flattened to its components
+pub trait Coordinate: Clone {
+    fn dim(&self) -> usize;
+    fn get(&self, i: usize) -> f64;
+    fn from_slice(v: &[f64]) -> Self;
+
+    fn to_vec(&self) -> Vec<f64> {
+        (0..self.dim()).map(|i| self.get(i)).collect()
+    }
+
+    fn norm_sq(&self) -> f64 {
+        (0..self.dim()).map(|i| self.get(i).powi(2)).sum()
+    }
+}
+
+impl Coordinate for f64 {
+    fn dim(&self) -> usize {
+        1
+    }
+
+    fn get(&self, _i: usize) -> f64 {
+        *self
+    }
+
+    fn from_slice(v: &[f64]) -> Self {
+        v[0]
+    }
+
+    fn norm_sq(&self) -> f64 {
+        self.powi(2)
+    }
+}
+
+impl Coordinate for (f64, f64) {
+    fn dim(&self) -> usize {
+        2
+    }
+
+    fn get(&self, i: usize) -> f64 {
+        match i {
+            0 => self.0,
+            1 => self.1,
+            _ => panic!("index out of range for 2D coordinate"),
+        }
+    }
+
+    fn from_slice(v: &[f64]) -> Self {
+        (v[0], v[1])
+    }
+
+    fn norm_sq(&self) -> f64 {
+        self.0.powi(2) + self.1.powi(2)
+    }
+}
+
+impl<const N: usize> Coordinate for [f64; N] {
+    fn dim(&self) -> usize {
+        N
+    }
+
+    fn get(&self, i: usize) -> f64 {
+        self[i]
+    }
+
+    fn from_slice(v: &[f64]) -> Self {
+        let mut q = [0f64; N];
+        q.copy_from_slice(&v[..N]);
+        q
+    }
+}
+
+impl Coordinate for Vec<f64> {
+    fn dim(&self) -> usize {
+        self.len()
+    }
+
+    fn get(&self, i: usize) -> f64 {
+        self[i]
+    }
+
+    fn from_slice(v: &[f64]) -> Self {
+        v.to_vec()
+    }
+
+    fn to_vec(&self) -> Vec<f64> {
+        self.clone()
+    }
+}
+
+pub fn midpoint<Q: Coordinate>(q0: &Q, q1: &Q) -> Q {
+    let v = q0
+        .to_vec()
+        .iter()
+        .zip(q1.to_vec())
+        .map(|(a, b)| 0.5 * (a + b))
+        .collect::<Vec<f64>>();
+    Q::from_slice(&v)
+}
+
+pub fn velocity<Q: Coordinate>(q0: &Q, q1: &Q, dt: f64) -> Q {
+    let v = q0
+        .to_vec()
+        .iter()
+        .zip(q1.to_vec())
+        .map(|(a, b)| (b - a) / dt)
+        .collect::<Vec<f64>>();
+    Q::from_slice(&v)
+}
@@ -0,0 +1,117 @@
+use crate::coordinate::{midpoint, velocity, Coordinate};
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+
+/// Midpoint rule discrete Lagrangian
+///
+/// L_d(q_k, q_{k+1}) = dt * L(t_{k+1/2}, (q_k + q_{k+1}) / 2, (q_{k+1} - q_k) / dt)
+///
+/// Segment methods take the midpoint time t_{k+1/2}; paths start at t = 0
+pub struct DiscreteLagrangian<'a, L: Lagrangian> {
+    lagrangian: &'a L,
+    dt: f64,
+}
+
+impl<'a, L: Lagrangian> DiscreteLagrangian<'a, L> {
+    pub fn new(lagrangian: &'a L, dt: f64) -> Self {
+        Self { lagrangian, dt }
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    pub fn calc(&self, t: f64, q0: &L::Q, q1: &L::Q) -> f64 {
+        let q = midpoint(q0, q1);
+        let dq = velocity(q0, q1, self.dt);
+        self.dt * self.lagrangian.calc_t(t, &q, &dq)
+    }
+
+    /// Whether the segment stays inside the domain of L
+    pub fn is_admissible(&self, q0: &L::Q, q1: &L::Q) -> bool {
+        let q = midpoint(q0, q1);
+        let dq = velocity(q0, q1, self.dt);
+        self.lagrangian.is_admissible(&q, &dq)
+    }
+
+    /// Midpoint time of the k-th segment
+    pub fn t_mid(&self, k: usize) -> f64 {
+        (k as f64 + 0.5) * self.dt
+    }
+
+    /// (∂L/∂q, ∂L/∂q̇) at the segment midpoint
+    fn slopes(&self, t: f64, q0: &L::Q, q1: &L::Q) -> (Vec<f64>, Vec<f64>) {
+        let q = midpoint(q0, q1);
+        let dq = velocity(q0, q1, self.dt);
+        (
+            self.lagrangian.dl_dq(t, &q, &dq).to_vec(),
+            self.lagrangian.dl_ddq(t, &q, &dq).to_vec(),
+        )
+    }
+
+    /// ∂L_d/∂q_k
+    pub fn d1(&self, t: f64, q0: &L::Q, q1: &L::Q) -> Vec<f64> {
+        let (l_q, l_dq) = self.slopes(t, q0, q1);
+        l_q.iter()
+            .zip(l_dq.iter())
+            .map(|(a, b)| 0.5 * self.dt * a - b)
+            .collect()
+    }
+
+    /// ∂L_d/∂q_{k+1}
+    pub fn d2(&self, t: f64, q0: &L::Q, q1: &L::Q) -> Vec<f64> {
+        let (l_q, l_dq) = self.slopes(t, q0, q1);
+        l_q.iter()
+            .zip(l_dq.iter())
+            .map(|(a, b)| 0.5 * self.dt * a + b)
+            .collect()
+    }
+
+    /// (∂²L_d/∂q_k², ∂²L_d/∂q_k∂q_{k+1}, ∂²L_d/∂q_{k+1}²)
+    pub fn hessian(&self, t: f64, q0: &L::Q, q1: &L::Q) -> (Matrix, Matrix, Matrix) {
+        let q = midpoint(q0, q1);
+        let dq = velocity(q0, q1, self.dt);
+        let h = self.lagrangian.hessian(t, &q, &dq);
+        let n = q.dim();
+        let dt = self.dt;
+
+        // q_k enters as (q, q̇) = (1/2, -1/dt), q_{k+1} as (1/2, 1/dt)
+        let block = |c0: (f64, f64), c1: (f64, f64)| {
+            let mut m = zeros(n, n);
+            for i in 0..n {
+                for j in 0..n {
+                    m[(i, j)] = dt
+                        * (c0.0 * c1.0 * h[(i, j)]
+                            + c0.0 * c1.1 * h[(i, n + j)]
+                            + c0.1 * c1.0 * h[(n + i, j)]
+                            + c0.1 * c1.1 * h[(n + i, n + j)]);
+                }
+            }
+            m
+        };
+        let c0 = (0.5, -1f64 / dt);
+        let c1 = (0.5, 1f64 / dt);
+        (block(c0, c0), block(c0, c1), block(c1, c1))
+    }
+
+    pub fn action(&self, path: &[L::Q]) -> f64 {
+        path.windows(2)
+            .enumerate()
+            .map(|(k, w)| self.calc(self.t_mid(k), &w[0], &w[1]))
+            .sum()
+    }
+
+    /// D2 L_d(q_{k-1}, q_k) + D1 L_d(q_k, q_{k+1}) at every interior node
+    pub fn el_residual(&self, path: &[L::Q]) -> Vec<Vec<f64>> {
+        path.windows(3)
+            .enumerate()
+            .map(|(k, w)| {
+                self.d2(self.t_mid(k), &w[0], &w[1])
+                    .iter()
+                    .zip(self.d1(self.t_mid(k + 1), &w[1], &w[2]))
+                    .map(|(a, b)| a + b)
+                    .collect()
+            })
+            .collect()
+    }
+}
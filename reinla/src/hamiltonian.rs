@@ -0,0 +1,214 @@
+use crate::coordinate::{midpoint, velocity, Coordinate};
+use crate::discrete::DiscreteLagrangian;
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+
+/// Canonical momentum p = ∂L/∂q̇
+pub fn momentum<L: Lagrangian>(lagrangian: &L, t: f64, q: &L::Q, dq: &L::Q) -> L::Q {
+    lagrangian.dl_ddq(t, q, dq)
+}
+
+/// Energy function E = p·q̇ - L
+pub fn energy_function<L: Lagrangian>(lagrangian: &L, t: f64, q: &L::Q, dq: &L::Q) -> f64 {
+    let p = momentum(lagrangian, t, q, dq).to_vec();
+    p.dot(&dq.to_vec()) - lagrangian.calc_t(t, q, dq)
+}
+
+/// Continuous symmetry of a Lagrangian
+#[derive(Debug, Clone)]
+pub enum Symmetry {
+    /// q → q + ε d conserves p·d
+    Translation(Vec<f64>),
+    /// t → t + ε conserves the energy function
+    TimeTranslation,
+    /// Rotation in the (i, j) plane conserves q_i p_j - q_j p_i
+    Rotation(usize, usize),
+}
+
+impl Symmetry {
+    /// Rotation about the origin of a 2D Cartesian coordinate
+    pub fn rotation_2d() -> Self {
+        Symmetry::Rotation(0, 1)
+    }
+}
+
+/// Noether charge of `symmetry` at (t, q, q̇)
+pub fn noether_charge<L: Lagrangian>(
+    lagrangian: &L,
+    symmetry: &Symmetry,
+    t: f64,
+    q: &L::Q,
+    dq: &L::Q,
+) -> f64 {
+    match symmetry {
+        Symmetry::TimeTranslation => energy_function(lagrangian, t, q, dq),
+        _ => momentum_charge(
+            symmetry,
+            &q.to_vec(),
+            &momentum(lagrangian, t, q, dq).to_vec(),
+        ),
+    }
+}
+
+/// Charge of a translation or rotation at position q with momentum p
+fn momentum_charge(symmetry: &Symmetry, q: &[f64], p: &[f64]) -> f64 {
+    match symmetry {
+        Symmetry::Translation(d) => p.iter().zip(d).map(|(a, b)| a * b).sum(),
+        Symmetry::Rotation(i, j) => q[*i] * p[*j] - q[*j] * p[*i],
+        Symmetry::TimeTranslation => unreachable!("energy is not a momentum charge"),
+    }
+}
+
+/// Evaluates `f(t, q, q̇)` at every segment midpoint of `path`, which starts at t = 0
+fn along<L: Lagrangian, T, F: Fn(f64, &L::Q, &L::Q) -> T>(path: &[L::Q], dt: f64, f: F) -> Vec<T> {
+    path.windows(2)
+        .enumerate()
+        .map(|(k, w)| {
+            let t = (k as f64 + 0.5) * dt;
+            f(t, &midpoint(&w[0], &w[1]), &velocity(&w[0], &w[1], dt))
+        })
+        .collect()
+}
+
+/// Canonical momentum at every segment midpoint of `path`
+pub fn momenta<L: Lagrangian>(lagrangian: &L, path: &[L::Q], dt: f64) -> Vec<L::Q> {
+    along::<L, _, _>(path, dt, |t, q, dq| momentum(lagrangian, t, q, dq))
+}
+
+/// Discrete momentum at every node of `path`: p_0 = -D1 L_d(q_0, q_1) and
+/// p_k = D2 L_d(q_{k-1}, q_k), which discrete Euler-Lagrange paths conserve exactly
+///
+/// Empty if `path` has no segment
+pub fn discrete_momenta<L: Lagrangian>(lagrangian: &L, path: &[L::Q], dt: f64) -> Vec<L::Q> {
+    if path.len() < 2 {
+        return vec![];
+    }
+    let discrete = DiscreteLagrangian::new(lagrangian, dt);
+    let p0 = discrete
+        .d1(discrete.t_mid(0), &path[0], &path[1])
+        .mul_s(-1f64);
+    let mut p = vec![L::Q::from_slice(&p0)];
+    p.extend(
+        path.windows(2)
+            .enumerate()
+            .map(|(k, w)| L::Q::from_slice(&discrete.d2(discrete.t_mid(k), &w[0], &w[1]))),
+    );
+    p
+}
+
+/// Energy function at every segment midpoint of `path`
+pub fn energy<L: Lagrangian>(lagrangian: &L, path: &[L::Q], dt: f64) -> Vec<f64> {
+    along::<L, _, _>(path, dt, |t, q, dq| energy_function(lagrangian, t, q, dq))
+}
+
+/// Noether charge along `path`
+///
+/// Translations and rotations use the discrete momentum at every node, so they are conserved
+/// to round-off on discrete Euler-Lagrange paths. The midpoint rule has no exactly conserved
+/// energy at a fixed step, so `TimeTranslation` gives the energy function at every segment
+/// midpoint, which only stays close to constant
+pub fn charges<L: Lagrangian>(
+    lagrangian: &L,
+    symmetry: &Symmetry,
+    path: &[L::Q],
+    dt: f64,
+) -> Vec<f64> {
+    match symmetry {
+        Symmetry::TimeTranslation => energy(lagrangian, path, dt),
+        _ => path
+            .iter()
+            .zip(discrete_momenta(lagrangian, path, dt))
+            .map(|(q, p)| momentum_charge(symmetry, &q.to_vec(), &p.to_vec()))
+            .collect(),
+    }
+}
+
+/// How well a charge is conserved along a path
+#[derive(Debug, Clone)]
+pub struct Conservation {
+    pub values: Vec<f64>,
+    /// max_k |Q_k - Q_0|
+    pub max_drift: f64,
+    /// `max_drift` relative to |Q_0| (absolute when Q_0 = 0)
+    pub relative_drift: f64,
+}
+
+/// `None` if `path` has no segment to evaluate the charge on
+pub fn conservation<L: Lagrangian>(
+    lagrangian: &L,
+    symmetry: &Symmetry,
+    path: &[L::Q],
+    dt: f64,
+) -> Option<Conservation> {
+    let values = charges(lagrangian, symmetry, path, dt);
+    let q0 = *values.first()?;
+    let max_drift = values.iter().fold(0f64, |acc, q| acc.max((q - q0).abs()));
+    let relative_drift = if q0 == 0f64 {
+        max_drift
+    } else {
+        max_drift / q0.abs()
+    };
+    Some(Conservation {
+        values,
+        max_drift,
+        relative_drift,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrator::VariationalIntegrator;
+    use crate::lagrangian::two_dim::{CentralForce, CentralPotential};
+
+    const DT: f64 = 0.01;
+
+    fn kepler_orbit() -> (CentralForce, Vec<(f64, f64)>) {
+        let kepler = CentralForce::new(1f64, CentralPotential::Kepler { k: 1f64 });
+        let path = VariationalIntegrator::new(&kepler, DT)
+            .integrate((1f64, 0f64), (1f64, 1.2 * DT), 2000)
+            .unwrap();
+        (kepler, path)
+    }
+
+    #[test]
+    fn discrete_angular_momentum_is_conserved() {
+        let (kepler, path) = kepler_orbit();
+        let c = conservation(&kepler, &Symmetry::rotation_2d(), &path, DT).unwrap();
+        assert_eq!(c.values.len(), path.len());
+        assert!(c.relative_drift < 1e-10, "{}", c.relative_drift);
+
+        // Midpoint momenta are only conserved up to O(dt²)
+        let p = momenta(&kepler, &path, DT);
+        let l = path
+            .windows(2)
+            .zip(p)
+            .map(|(w, p)| {
+                let q = midpoint(&w[0], &w[1]);
+                q.0 * p.1 - q.1 * p.0
+            })
+            .collect::<Vec<f64>>();
+        assert!(l.iter().any(|x| (x - l[0]).abs() > 1e-8));
+    }
+
+    #[test]
+    fn non_symmetry_is_not_conserved() {
+        let (kepler, path) = kepler_orbit();
+        let c = conservation(&kepler, &Symmetry::Translation(vec![1f64, 0f64]), &path, DT).unwrap();
+        assert!(c.max_drift > 1e-1);
+
+        let c = conservation(&kepler, &Symmetry::TimeTranslation, &path, DT).unwrap();
+        assert_eq!(c.values.len(), path.len() - 1);
+        assert!(c.relative_drift < 1e-3);
+    }
+
+    #[test]
+    fn single_node_has_no_charge() {
+        let kepler = CentralForce::new(1f64, CentralPotential::Kepler { k: 1f64 });
+        let path = [(1f64, 0f64)];
+        assert!(discrete_momenta(&kepler, &path, DT).is_empty());
+        for symmetry in [Symmetry::TimeTranslation, Symmetry::rotation_2d()] {
+            assert!(conservation(&kepler, &symmetry, &path, DT).is_none());
+        }
+    }
+}
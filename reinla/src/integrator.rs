@@ -0,0 +1,150 @@
+use crate::coordinate::Coordinate;
+use crate::discrete::DiscreteLagrangian;
+use crate::hamiltonian;
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+
+/// Variational integrator for the midpoint discrete Lagrangian
+///
+/// Given (q_{k-1}, q_k), solves D2 L_d(q_{k-1}, q_k) + D1 L_d(q_k, q_{k+1}) = 0 for q_{k+1}
+pub struct VariationalIntegrator<'a, L: Lagrangian> {
+    discrete: DiscreteLagrangian<'a, L>,
+    lagrangian: &'a L,
+    tol: f64,
+    max_iter: usize,
+}
+
+impl<'a, L: Lagrangian> VariationalIntegrator<'a, L> {
+    pub fn new(lagrangian: &'a L, dt: f64) -> Self {
+        Self {
+            discrete: DiscreteLagrangian::new(lagrangian, dt),
+            lagrangian,
+            tol: 1e-12,
+            max_iter: 50,
+        }
+    }
+
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.discrete.dt()
+    }
+
+    /// Newton iteration for q_{k+1} with q_k at time t, starting from linear extrapolation
+    ///
+    /// Returns `None` if the Newton step does not drop below `tol` within `max_iter` iterations
+    pub fn step(&self, t: f64, q0: &L::Q, q1: &L::Q) -> Option<L::Q> {
+        let dt = self.dt();
+        let p = self.discrete.d2(t - 0.5 * dt, q0, q1);
+        let mut q2 = q1
+            .to_vec()
+            .iter()
+            .zip(q0.to_vec())
+            .map(|(b, a)| 2f64 * b - a)
+            .collect::<Vec<f64>>();
+
+        for _ in 0..self.max_iter {
+            let q2_q = L::Q::from_slice(&q2);
+            let f = p.add_v(&self.discrete.d1(t + 0.5 * dt, q1, &q2_q));
+            let (_, jacobian, _) = self.discrete.hessian(t + 0.5 * dt, q1, &q2_q);
+            let delta = jacobian.solve(&f, SolveKind::LU);
+            q2 = q2.sub_v(&delta);
+
+            let scale = q2.iter().fold(1f64, |acc, x| acc.max(x.abs()));
+            if delta.iter().all(|d| d.abs() <= self.tol * scale) {
+                return Some(L::Q::from_slice(&q2));
+            }
+        }
+        None
+    }
+
+    /// Trajectory q_0, q_1, ..., q_{n_steps} starting at t = 0
+    ///
+    /// Returns `None` if any step fails to converge
+    pub fn integrate(&self, q0: L::Q, q1: L::Q, n_steps: usize) -> Option<Vec<L::Q>> {
+        assert!(n_steps >= 1, "need at least one step to hold q_0 and q_1");
+        let mut path = vec![q0, q1];
+        for k in 2..=n_steps {
+            let t = (k - 1) as f64 * self.dt();
+            let q_next = self.step(t, &path[k - 2], &path[k - 1])?;
+            path.push(q_next);
+        }
+        Some(path)
+    }
+
+    /// Energy p·q̇ - L at each segment midpoint of `path`
+    pub fn energy(&self, path: &[L::Q]) -> Vec<f64> {
+        hamiltonian::energy(self.lagrangian, path, self.dt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrangian::one_dim::SHO;
+    use std::f64::consts::PI;
+
+    const DT: f64 = 0.1;
+
+    /// Exact solution of the midpoint rule SHO (m = k = 1) with q_0 = 1, q_1 = cos θ:
+    /// q_k = cos(k θ) with cos θ = (1 - dt²/4) / (1 + dt²/4)
+    fn theta() -> f64 {
+        let h = DT.powi(2) / 4f64;
+        ((1f64 - h) / (1f64 + h)).acos()
+    }
+
+    #[test]
+    fn sho_matches_discrete_solution() {
+        let sho = SHO::new(1f64, 1f64);
+        let theta = theta();
+        let path = VariationalIntegrator::new(&sho, DT)
+            .integrate(1f64, theta.cos(), 1000)
+            .unwrap();
+        assert_eq!(path.len(), 1001);
+        for (k, q) in path.iter().enumerate() {
+            assert!((q - (k as f64 * theta).cos()).abs() < 1e-9, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn sho_energy_stays_bounded() {
+        let sho = SHO::new(1f64, 1f64);
+        let integrator = VariationalIntegrator::new(&sho, DT);
+        // 100 periods
+        let n_steps = (200f64 * PI / DT) as usize;
+        let path = integrator.integrate(1f64, DT.cos(), n_steps).unwrap();
+        let energy = integrator.energy(&path);
+        let e0 = energy[0];
+        let drift = energy
+            .iter()
+            .fold(0f64, |acc, e| acc.max((e - e0).abs() / e0.abs()));
+        // The midpoint energy of a quadratic Lagrangian is conserved up to round-off
+        assert!(drift < 1e-10, "drift = {}", drift);
+    }
+
+    #[test]
+    fn one_step_holds_both_initial_nodes() {
+        let sho = SHO::new(1f64, 1f64);
+        let path = VariationalIntegrator::new(&sho, DT)
+            .integrate(1f64, 0.9, 1)
+            .unwrap();
+        assert_eq!(path, vec![1f64, 0.9]);
+    }
+
+    #[test]
+    fn unconverged_step_is_none() {
+        let sho = SHO::new(1f64, 1f64);
+        let mut integrator = VariationalIntegrator::new(&sho, DT);
+        // Newton converges in one step on a quadratic Lagrangian, but only knows after two
+        integrator.set_max_iter(1);
+        assert!(integrator.step(DT, &1f64, &0.9).is_none());
+        integrator.set_max_iter(2);
+        assert!(integrator.step(DT, &1f64, &0.9).is_some());
+    }
+}
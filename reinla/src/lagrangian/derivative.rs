@@ -0,0 +1,198 @@
+use crate::coordinate::Coordinate;
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+
+// peroxide 0.34 gets the signs of cos' and sin'' wrong on dual numbers,
+// so trigonometric Lagrangians should use these instead of `AD::sin`/`AD::cos`
+fn chain(x: AD, f: f64, df: f64, ddf: f64) -> AD {
+    match x {
+        AD0(_) => AD0(f),
+        AD1(_, dx) => AD1(f, df * dx),
+        AD2(_, dx, ddx) => AD2(f, df * dx, ddf * dx * dx + df * ddx),
+    }
+}
+
+pub fn sin(x: AD) -> AD {
+    let (s, c) = x.x().sin_cos();
+    chain(x, s, c, -s)
+}
+
+pub fn cos(x: AD) -> AD {
+    let (s, c) = x.x().sin_cos();
+    chain(x, c, -s, -c)
+}
+
+// Phase space point x = (q, dq) is flattened as [q_0, ..., q_n, dq_0, ..., dq_n]
+fn split<Q: Coordinate>(x: &[f64]) -> (Q, Q) {
+    let n = x.len() / 2;
+    (Q::from_slice(&x[..n]), Q::from_slice(&x[n..]))
+}
+
+fn flatten<Q: Coordinate>(q: &Q, dq: &Q) -> Vec<f64> {
+    let mut x = q.to_vec();
+    x.extend(dq.to_vec());
+    x
+}
+
+fn eval<L: Lagrangian + ?Sized>(lagrangian: &L, t: f64, x: &[f64]) -> f64 {
+    let (q, dq) = split::<L::Q>(x);
+    lagrangian.calc_t(t, &q, &dq)
+}
+
+/// Directional derivatives of L along `v` up to second order via dual numbers
+fn eval_ad<L: Lagrangian + ?Sized>(lagrangian: &L, t: f64, x: &[f64], v: &[f64]) -> Option<AD> {
+    let n = x.len() / 2;
+    let x_ad = x
+        .iter()
+        .zip(v.iter())
+        .map(|(&x, &v)| AD2(x, v, 0f64))
+        .collect::<Vec<AD>>();
+    lagrangian.calc_ad(t, &x_ad[..n], &x_ad[n..])
+}
+
+fn unit(n: usize, i: usize) -> Vec<f64> {
+    let mut e = vec![0f64; n];
+    e[i] = 1f64;
+    e
+}
+
+fn step(x: f64, h: f64) -> f64 {
+    h * x.abs().max(1f64)
+}
+
+/// Gradient of L w.r.t. (q, dq)
+pub fn gradient<L: Lagrangian + ?Sized>(
+    lagrangian: &L,
+    t: f64,
+    q: &L::Q,
+    dq: &L::Q,
+) -> (Vec<f64>, Vec<f64>) {
+    let x = flatten(q, dq);
+    let n = x.len();
+
+    // Probe AD support along e_0 and keep that component
+    let grad = match eval_ad(lagrangian, t, &x, &unit(n, 0)) {
+        Some(d0) => std::iter::once(d0.dx())
+            .chain((1..n).map(|i| eval_ad(lagrangian, t, &x, &unit(n, i)).unwrap().dx()))
+            .collect::<Vec<f64>>(),
+        None => (0..n)
+            .map(|i| {
+                let h = step(x[i], 1e-6);
+                let mut x_p = x.clone();
+                let mut x_m = x.clone();
+                x_p[i] += h;
+                x_m[i] -= h;
+                (eval(lagrangian, t, &x_p) - eval(lagrangian, t, &x_m)) / (2f64 * h)
+            })
+            .collect(),
+    };
+
+    let dl_ddq = grad[n / 2..].to_vec();
+    let mut dl_dq = grad;
+    dl_dq.truncate(n / 2);
+    (dl_dq, dl_ddq)
+}
+
+/// Hessian of L w.r.t. (q, dq)
+pub fn hessian<L: Lagrangian + ?Sized>(lagrangian: &L, t: f64, q: &L::Q, dq: &L::Q) -> Matrix {
+    let x = flatten(q, dq);
+    let n = x.len();
+    let mut h = zeros(n, n);
+
+    if let Some(d0) = eval_ad(lagrangian, t, &x, &unit(n, 0)) {
+        // vᵀHv along e_i and e_i + e_j recovers the mixed partials
+        let diag = std::iter::once(d0.ddx())
+            .chain((1..n).map(|i| eval_ad(lagrangian, t, &x, &unit(n, i)).unwrap().ddx()))
+            .collect::<Vec<f64>>();
+        for i in 0..n {
+            h[(i, i)] = diag[i];
+            for j in i + 1..n {
+                let v = unit(n, i).add_v(&unit(n, j));
+                let d = eval_ad(lagrangian, t, &x, &v).unwrap().ddx();
+                h[(i, j)] = 0.5 * (d - diag[i] - diag[j]);
+                h[(j, i)] = h[(i, j)];
+            }
+        }
+    } else {
+        for i in 0..n {
+            for j in i..n {
+                let h_i = step(x[i], 1e-4);
+                let h_j = step(x[j], 1e-4);
+                let f = |s_i: f64, s_j: f64| {
+                    let mut x_s = x.clone();
+                    x_s[i] += s_i * h_i;
+                    x_s[j] += s_j * h_j;
+                    eval(lagrangian, t, &x_s)
+                };
+                h[(i, j)] = (f(1f64, 1f64) - f(1f64, -1f64) - f(-1f64, 1f64) + f(-1f64, -1f64))
+                    / (4f64 * h_i * h_j);
+                h[(j, i)] = h[(i, j)];
+            }
+        }
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrangian::n_dim::{FreeBody, SHO};
+
+    /// Hides `calc_ad` of the inner Lagrangian to force finite differences
+    struct NoAD<L>(L);
+
+    impl<L: Lagrangian> Lagrangian for NoAD<L> {
+        type Q = L::Q;
+
+        fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+            self.0.calc(q, dq)
+        }
+
+        fn calc_t(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> f64 {
+            self.0.calc_t(t, q, dq)
+        }
+    }
+
+    fn assert_ad_matches_fd<L: Lagrangian>(lagrangian: L, q: &L::Q, dq: &L::Q) {
+        let (ad_q, ad_dq) = gradient(&lagrangian, 0f64, q, dq);
+        let ad_h = hessian(&lagrangian, 0f64, q, dq);
+        let fallback = NoAD(lagrangian);
+        let (fd_q, fd_dq) = gradient(&fallback, 0f64, q, dq);
+        let fd_h = hessian(&fallback, 0f64, q, dq);
+
+        for (a, b) in ad_q.iter().chain(&ad_dq).zip(fd_q.iter().chain(&fd_dq)) {
+            assert!((a - b).abs() < 1e-6, "gradient {a} vs {b}");
+        }
+        for i in 0..ad_h.row {
+            for j in 0..ad_h.col {
+                let (a, b) = (ad_h[(i, j)], fd_h[(i, j)]);
+                assert!((a - b).abs() < 1e-4, "hessian ({i}, {j}) {a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn free_body_ad_matches_finite_differences() {
+        assert_ad_matches_fd(FreeBody::<f64>::new(2f64), &0.3, &-1.2);
+        assert_ad_matches_fd(FreeBody::<[f64; 2]>::new(2f64), &[0.3, 1f64], &[-1.2, 0.5]);
+    }
+
+    #[test]
+    fn sho_ad_matches_finite_differences() {
+        assert_ad_matches_fd(SHO::<f64>::new(2f64, 3f64), &0.7, &0.4);
+        assert_ad_matches_fd(SHO::<[f64; 2]>::new(2f64, 3f64), &[0.7, -0.2], &[0.4, 1.5]);
+    }
+
+    #[test]
+    fn sho_derivatives_are_exact() {
+        let sho = SHO::<f64>::new(2f64, 3f64);
+        let (l_q, l_dq) = gradient(&NoAD(sho), 0f64, &0.7, &0.4);
+        assert!((l_q[0] + 3f64 * 0.7).abs() < 1e-8);
+        assert!((l_dq[0] - 2f64 * 0.4).abs() < 1e-8);
+
+        let h = hessian(&SHO::<f64>::new(2f64, 3f64), 0f64, &0.7, &0.4);
+        assert_eq!(h[(0, 0)], -3f64);
+        assert_eq!(h[(1, 1)], 2f64);
+        assert_eq!(h[(0, 1)], 0f64);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::coordinate::Coordinate;
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+use std::marker::PhantomData;
+
+pub type ScalarFn = Box<dyn Fn(&[f64]) -> f64>;
+pub type VectorFn = Box<dyn Fn(&[f64]) -> [f64; 3]>;
+
+/// Pads a 2D or 3D position to three components
+fn xyz<T: Copy>(q: &[T], zero: T) -> [T; 3] {
+    match q.len() {
+        2 => [q[0], q[1], zero],
+        3 => [q[0], q[1], q[2]],
+        _ => panic!("charged particle needs a 2D or 3D coordinate"),
+    }
+}
+
+fn norm(r: &[f64; 3]) -> f64 {
+    (r[0].powi(2) + r[1].powi(2) + r[2].powi(2)).sqrt()
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Electric Scalar Potential φ
+// └──────────────────────────────────────────────────────────┘
+pub enum ScalarPotential {
+    Zero,
+    /// Uniform electric field E, φ = -E·r
+    Uniform {
+        e: [f64; 3],
+    },
+    /// Point charge at the origin, φ = k / |r|
+    Coulomb {
+        k: f64,
+    },
+    Custom(ScalarFn),
+}
+
+impl ScalarPotential {
+    pub fn eval(&self, q: &[f64]) -> f64 {
+        let r = xyz(q, 0f64);
+        match self {
+            ScalarPotential::Zero => 0f64,
+            ScalarPotential::Uniform { e } => -(e[0] * r[0] + e[1] * r[1] + e[2] * r[2]),
+            ScalarPotential::Coulomb { k } => k / norm(&r),
+            ScalarPotential::Custom(f) => f(q),
+        }
+    }
+
+    pub fn eval_ad(&self, q: &[AD]) -> Option<AD> {
+        let r = xyz(q, AD0(0f64));
+        match self {
+            ScalarPotential::Zero => Some(AD0(0f64)),
+            ScalarPotential::Uniform { e } => Some(-(e[0] * r[0] + e[1] * r[1] + e[2] * r[2])),
+            ScalarPotential::Coulomb { k } => {
+                let r2 = q.iter().fold(AD0(0f64), |acc, x| acc + x.powi(2));
+                Some(*k / r2.sqrt())
+            }
+            ScalarPotential::Custom(_) => None,
+        }
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Magnetic Vector Potential A
+// └──────────────────────────────────────────────────────────┘
+pub enum VectorPotential {
+    Zero,
+    /// Uniform B along z in the symmetric gauge, A = B/2 (-y, x, 0)
+    UniformSymmetric {
+        b: f64,
+    },
+    /// Uniform B along z in the Landau gauge, A = (-B y, 0, 0)
+    UniformLandau {
+        b: f64,
+    },
+    /// Point dipole along z at the origin, A = m (-y, x, 0) / |r|³
+    Dipole {
+        moment: f64,
+    },
+    Custom(VectorFn),
+}
+
+impl VectorPotential {
+    pub fn eval(&self, q: &[f64]) -> [f64; 3] {
+        let r = xyz(q, 0f64);
+        match self {
+            VectorPotential::Zero => [0f64; 3],
+            VectorPotential::UniformSymmetric { b } => [-0.5 * b * r[1], 0.5 * b * r[0], 0f64],
+            VectorPotential::UniformLandau { b } => [-b * r[1], 0f64, 0f64],
+            VectorPotential::Dipole { moment } => {
+                let c = moment / norm(&r).powi(3);
+                [-c * r[1], c * r[0], 0f64]
+            }
+            VectorPotential::Custom(f) => f(q),
+        }
+    }
+
+    pub fn eval_ad(&self, q: &[AD]) -> Option<[AD; 3]> {
+        let zero = AD0(0f64);
+        let r = xyz(q, zero);
+        match self {
+            VectorPotential::Zero => Some([zero; 3]),
+            VectorPotential::UniformSymmetric { b } => {
+                Some([-0.5 * b * r[1], 0.5 * b * r[0], zero])
+            }
+            VectorPotential::UniformLandau { b } => Some([-b * r[1], zero, zero]),
+            VectorPotential::Dipole { moment } => {
+                let r2 = q.iter().fold(zero, |acc, x| acc + x.powi(2));
+                let c = *moment / r2.powf(1.5);
+                Some([-c * r[1], c * r[0], zero])
+            }
+            VectorPotential::Custom(_) => None,
+        }
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Charged Particle: L = m v²/2 - e φ + e v·A
+// └──────────────────────────────────────────────────────────┘
+pub struct ChargedParticle<Q> {
+    mass: f64,
+    charge: f64,
+    scalar: ScalarPotential,
+    vector: VectorPotential,
+    _q: PhantomData<Q>,
+}
+
+impl<Q: Coordinate> ChargedParticle<Q> {
+    pub fn new(mass: f64, charge: f64, scalar: ScalarPotential, vector: VectorPotential) -> Self {
+        Self {
+            mass,
+            charge,
+            scalar,
+            vector,
+            _q: PhantomData,
+        }
+    }
+
+    pub fn get_scalar_potential(&self) -> &ScalarPotential {
+        &self.scalar
+    }
+
+    pub fn get_vector_potential(&self) -> &VectorPotential {
+        &self.vector
+    }
+}
+
+impl<Q: Coordinate> Lagrangian for ChargedParticle<Q> {
+    type Q = Q;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        let q = q.to_vec();
+        let v = xyz(&dq.to_vec(), 0f64);
+        let a = self.vector.eval(&q);
+        let v_dot_a = v[0] * a[0] + v[1] * a[1] + v[2] * a[2];
+        0.5 * self.mass * v.iter().map(|x| x.powi(2)).sum::<f64>()
+            - self.charge * self.scalar.eval(&q)
+            + self.charge * v_dot_a
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        let phi = self.scalar.eval_ad(q)?;
+        let a = self.vector.eval_ad(q)?;
+        let v = xyz(dq, AD0(0f64));
+        let v_dot_a = v[0] * a[0] + v[1] * a[1] + v[2] * a[2];
+        let v2 = v[0].powi(2) + v[1].powi(2) + v[2].powi(2);
+        Some(0.5 * self.mass * v2 - self.charge * phi + self.charge * v_dot_a)
+    }
+}
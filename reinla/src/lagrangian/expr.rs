@@ -0,0 +1,647 @@
+use crate::coordinate::Coordinate;
+use crate::lagrangian::{derivative, Lagrangian};
+use peroxide::fuga::*;
+use std::fmt;
+use std::marker::PhantomData;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Closure Lagrangian
+// └──────────────────────────────────────────────────────────┘
+/// Lagrangian from a closure `f(t, q, dq)`; derivatives use finite differences
+pub struct FnLagrangian<Q, F> {
+    f: F,
+    _q: PhantomData<Q>,
+}
+
+impl<Q: Coordinate, F: Fn(f64, &Q, &Q) -> f64> FnLagrangian<Q, F> {
+    pub fn new(f: F) -> Self {
+        Self { f, _q: PhantomData }
+    }
+}
+
+impl<Q: Coordinate, F: Fn(f64, &Q, &Q) -> f64> Lagrangian for FnLagrangian<Q, F> {
+    type Q = Q;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        self.calc_t(0f64, q, dq)
+    }
+
+    fn calc_t(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> f64 {
+        (self.f)(t, q, dq)
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Parse Error
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// Character that cannot start any token, with its byte offset
+    UnexpectedChar(usize, char),
+    /// Token that does not fit the grammar, with its byte offset
+    UnexpectedToken(usize, String),
+    UnexpectedEnd,
+    UnknownVariable(String),
+    UnknownFunction(String),
+    /// Parameter that shadows `q`, `dq`, `t` or a function
+    ReservedName(String),
+    /// Malformed `name=value` pair in a parameter list
+    InvalidParameter(String),
+    /// Parameter given more than once
+    DuplicateParameter(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedChar(pos, c) => {
+                write!(f, "unexpected character '{}' at {}", c, pos)
+            }
+            ParseError::UnexpectedToken(pos, token) => {
+                write!(f, "unexpected '{}' at {}", token, pos)
+            }
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ParseError::UnknownVariable(name) => write!(
+                f,
+                "unknown variable '{}' (expected q, dq, t or a parameter)",
+                name
+            ),
+            ParseError::UnknownFunction(name) => write!(f, "unknown function '{}'", name),
+            ParseError::ReservedName(name) => {
+                write!(f, "'{}' is reserved and cannot be a parameter", name)
+            }
+            ParseError::InvalidParameter(s) => {
+                write!(f, "invalid parameter '{}' (expected name=value)", s)
+            }
+            ParseError::DuplicateParameter(name) => {
+                write!(f, "parameter '{}' is given more than once", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Expression Tree
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Var {
+    Q,
+    DQ,
+    T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Func {
+    Sin,
+    Cos,
+    Exp,
+    Ln,
+    Sqrt,
+    Sinh,
+    Cosh,
+    Tanh,
+}
+
+impl Func {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sin" => Some(Func::Sin),
+            "cos" => Some(Func::Cos),
+            "exp" => Some(Func::Exp),
+            "ln" => Some(Func::Ln),
+            "sqrt" => Some(Func::Sqrt),
+            "sinh" => Some(Func::Sinh),
+            "cosh" => Some(Func::Cosh),
+            "tanh" => Some(Func::Tanh),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(f64),
+    Var(Var),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Pow(Box<Expr>, Box<Expr>),
+    Call(Func, Box<Expr>),
+}
+
+impl Expr {
+    fn is_constant(&self) -> bool {
+        match self {
+            Expr::Num(_) => true,
+            Expr::Var(_) => false,
+            Expr::Neg(a) | Expr::Call(_, a) => a.is_constant(),
+            Expr::Add(a, b)
+            | Expr::Sub(a, b)
+            | Expr::Mul(a, b)
+            | Expr::Div(a, b)
+            | Expr::Pow(a, b) => a.is_constant() && b.is_constant(),
+        }
+    }
+
+    /// Replace every subexpression free of q, dq and t by its value
+    fn fold(self) -> Expr {
+        if self.is_constant() {
+            return Expr::Num(self.eval(0f64, 0f64, 0f64));
+        }
+        let fold = |a: Box<Expr>| Box::new(a.fold());
+        match self {
+            Expr::Neg(a) => Expr::Neg(fold(a)),
+            Expr::Add(a, b) => Expr::Add(fold(a), fold(b)),
+            Expr::Sub(a, b) => Expr::Sub(fold(a), fold(b)),
+            Expr::Mul(a, b) => Expr::Mul(fold(a), fold(b)),
+            Expr::Div(a, b) => Expr::Div(fold(a), fold(b)),
+            Expr::Pow(a, b) => Expr::Pow(fold(a), fold(b)),
+            Expr::Call(func, a) => Expr::Call(func, fold(a)),
+            e => e,
+        }
+    }
+
+    fn eval(&self, t: f64, q: f64, dq: f64) -> f64 {
+        match self {
+            Expr::Num(x) => *x,
+            Expr::Var(Var::Q) => q,
+            Expr::Var(Var::DQ) => dq,
+            Expr::Var(Var::T) => t,
+            Expr::Neg(a) => -a.eval(t, q, dq),
+            Expr::Add(a, b) => a.eval(t, q, dq) + b.eval(t, q, dq),
+            Expr::Sub(a, b) => a.eval(t, q, dq) - b.eval(t, q, dq),
+            Expr::Mul(a, b) => a.eval(t, q, dq) * b.eval(t, q, dq),
+            Expr::Div(a, b) => a.eval(t, q, dq) / b.eval(t, q, dq),
+            // Constant exponents are folded into `Num` at parse time
+            Expr::Pow(a, b) => match **b {
+                Expr::Num(n) if n.fract() == 0f64 => a.eval(t, q, dq).powi(n as i32),
+                _ => a.eval(t, q, dq).powf(b.eval(t, q, dq)),
+            },
+            Expr::Call(func, a) => {
+                let x = a.eval(t, q, dq);
+                match func {
+                    Func::Sin => x.sin(),
+                    Func::Cos => x.cos(),
+                    Func::Exp => x.exp(),
+                    Func::Ln => x.ln(),
+                    Func::Sqrt => x.sqrt(),
+                    Func::Sinh => x.sinh(),
+                    Func::Cosh => x.cosh(),
+                    Func::Tanh => x.tanh(),
+                }
+            }
+        }
+    }
+
+    fn eval_ad(&self, t: f64, q: AD, dq: AD) -> AD {
+        match self {
+            Expr::Num(x) => AD0(*x),
+            Expr::Var(Var::Q) => q,
+            Expr::Var(Var::DQ) => dq,
+            Expr::Var(Var::T) => AD0(t),
+            Expr::Neg(a) => -a.eval_ad(t, q, dq),
+            Expr::Add(a, b) => a.eval_ad(t, q, dq) + b.eval_ad(t, q, dq),
+            Expr::Sub(a, b) => a.eval_ad(t, q, dq) - b.eval_ad(t, q, dq),
+            Expr::Mul(a, b) => a.eval_ad(t, q, dq) * b.eval_ad(t, q, dq),
+            Expr::Div(a, b) => a.eval_ad(t, q, dq) / b.eval_ad(t, q, dq),
+            // `AD::powi` of peroxide 0.34 returns its argument for n <= 0
+            Expr::Pow(a, b) => match **b {
+                Expr::Num(0f64) => AD0(1f64),
+                Expr::Num(n) if n.fract() == 0f64 && n > 0f64 => a.eval_ad(t, q, dq).powi(n as i32),
+                Expr::Num(n) if n.fract() == 0f64 => 1f64 / a.eval_ad(t, q, dq).powi(-n as i32),
+                Expr::Num(n) => a.eval_ad(t, q, dq).powf(n),
+                _ => (a.eval_ad(t, q, dq).ln() * b.eval_ad(t, q, dq)).exp(),
+            },
+            Expr::Call(func, a) => {
+                let x = a.eval_ad(t, q, dq);
+                match func {
+                    Func::Sin => derivative::sin(x),
+                    Func::Cos => derivative::cos(x),
+                    Func::Exp => x.exp(),
+                    Func::Ln => x.ln(),
+                    Func::Sqrt => x.sqrt(),
+                    Func::Sinh => x.sinh(),
+                    Func::Cosh => x.cosh(),
+                    Func::Tanh => x.tanh(),
+                }
+            }
+        }
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Tokenizer & Recursive Descent Parser
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Num(x) => write!(f, "{}", x),
+            Token::Ident(name) => write!(f, "{}", name),
+            Token::Op(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let chars = src.char_indices().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let (pos, c) = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].1.is_ascii_digit() || chars[i].1 == '.') {
+                i += 1;
+            }
+            // Exponent such as 1e-3
+            if i < chars.len() && (chars[i].1 == 'e' || chars[i].1 == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j].1 == '+' || chars[j].1 == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].1.is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].1.is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let end = if i < chars.len() {
+                chars[i].0
+            } else {
+                src.len()
+            };
+            let text = &src[pos..end];
+            let x = text
+                .parse::<f64>()
+                .map_err(|_| ParseError::UnexpectedToken(chars[start].0, text.to_string()))?;
+            tokens.push((pos, Token::Num(x)));
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                i += 1;
+            }
+            let end = if i < chars.len() {
+                chars[i].0
+            } else {
+                src.len()
+            };
+            tokens.push((pos, Token::Ident(src[pos..end].to_string())));
+        } else if "+-*/^()".contains(c) {
+            tokens.push((pos, Token::Op(c)));
+            i += 1;
+        } else {
+            return Err(ParseError::UnexpectedChar(pos, c));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    params: &'a [(&'a str, f64)],
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), ParseError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or(ParseError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, op: char) -> Result<(), ParseError> {
+        match self.next()? {
+            (_, Token::Op(c)) if c == op => Ok(()),
+            (pos, token) => Err(ParseError::UnexpectedToken(pos, token.to_string())),
+        }
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.term()?;
+        while let Some(Token::Op(c @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            lhs = match c {
+                '+' => Expr::Add(Box::new(lhs), Box::new(rhs)),
+                _ => Expr::Sub(Box::new(lhs), Box::new(rhs)),
+            };
+        }
+        Ok(lhs)
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Op(c @ ('*' | '/'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            lhs = match c {
+                '*' => Expr::Mul(Box::new(lhs), Box::new(rhs)),
+                _ => Expr::Div(Box::new(lhs), Box::new(rhs)),
+            };
+        }
+        Ok(lhs)
+    }
+
+    // unary := '-' unary | power
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if let Some(Token::Op('-')) = self.peek() {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    // power := primary ('^' unary)?, right associative
+    fn power(&mut self) -> Result<Expr, ParseError> {
+        let base = self.primary()?;
+        if let Some(Token::Op('^')) = self.peek() {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Ok(Expr::Pow(Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    // primary := number | variable | function '(' expr ')' | '(' expr ')'
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        match self.next()? {
+            (_, Token::Num(x)) => Ok(Expr::Num(x)),
+            (_, Token::Op('(')) => {
+                let e = self.expr()?;
+                self.expect(')')?;
+                Ok(e)
+            }
+            (_, Token::Ident(name)) => {
+                if let Some(Token::Op('(')) = self.peek() {
+                    let func = Func::from_name(&name).ok_or(ParseError::UnknownFunction(name))?;
+                    self.pos += 1;
+                    let arg = self.expr()?;
+                    self.expect(')')?;
+                    return Ok(Expr::Call(func, Box::new(arg)));
+                }
+                match name.as_str() {
+                    "q" => Ok(Expr::Var(Var::Q)),
+                    "dq" => Ok(Expr::Var(Var::DQ)),
+                    "t" => Ok(Expr::Var(Var::T)),
+                    _ => self
+                        .params
+                        .iter()
+                        .find(|(p, _)| *p == name)
+                        .map(|&(_, x)| Expr::Num(x))
+                        .ok_or(ParseError::UnknownVariable(name)),
+                }
+            }
+            (pos, token) => Err(ParseError::UnexpectedToken(pos, token.to_string())),
+        }
+    }
+}
+
+/// Rejects parameters that shadow variables or functions and repeated names
+fn check_params<'a, I: IntoIterator<Item = &'a str>>(names: I) -> Result<(), ParseError> {
+    let mut seen = vec![];
+    for name in names {
+        if ["q", "dq", "t"].contains(&name) || Func::from_name(name).is_some() {
+            return Err(ParseError::ReservedName(name.to_string()));
+        }
+        if seen.contains(&name) {
+            return Err(ParseError::DuplicateParameter(name.to_string()));
+        }
+        seen.push(name);
+    }
+    Ok(())
+}
+
+/// Parses a `name=value` list separated by commas, e.g. "m=1, g=9.8"
+pub fn parse_params(src: &str) -> Result<Vec<(String, f64)>, ParseError> {
+    let params = src
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (name, value) = s
+                .split_once('=')
+                .ok_or_else(|| ParseError::InvalidParameter(s.to_string()))?;
+            let name = name.trim();
+            let value = value
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| ParseError::InvalidParameter(s.to_string()))?;
+            let valid = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if !valid {
+                return Err(ParseError::InvalidParameter(s.to_string()));
+            }
+            Ok((name.to_string(), value))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    check_params(params.iter().map(|(name, _)| name.as_str()))?;
+    Ok(params)
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Expression Lagrangian
+// └──────────────────────────────────────────────────────────┘
+/// One dimensional Lagrangian parsed from an expression in `q`, `dq`, `t` and named parameters
+///
+/// Supports + - * / ^, unary minus, parentheses and sin, cos, exp, ln, sqrt, sinh, cosh, tanh
+#[derive(Debug, Clone)]
+pub struct ExprLagrangian {
+    source: String,
+    expr: Expr,
+}
+
+impl ExprLagrangian {
+    pub fn parse(src: &str, params: &[(&str, f64)]) -> Result<Self, ParseError> {
+        check_params(params.iter().map(|&(name, _)| name))?;
+        let mut parser = Parser {
+            tokens: tokenize(src)?,
+            pos: 0,
+            params,
+        };
+        let expr = parser.expr()?;
+        if let Ok((pos, token)) = parser.next() {
+            return Err(ParseError::UnexpectedToken(pos, token.to_string()));
+        }
+        Ok(Self {
+            source: src.to_string(),
+            expr: expr.fold(),
+        })
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl Lagrangian for ExprLagrangian {
+    type Q = f64;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        self.calc_t(0f64, q, dq)
+    }
+
+    fn calc_t(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> f64 {
+        self.expr.eval(t, *q, *dq)
+    }
+
+    fn calc_ad(&self, t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(self.expr.eval_ad(t, q[0], dq[0]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(src: &str, q: f64, dq: f64, t: f64) -> f64 {
+        ExprLagrangian::parse(src, &[("a", 5f64), ("b", 3f64), ("c", 2f64)])
+            .unwrap()
+            .calc_t(t, &q, &dq)
+    }
+
+    fn error(src: &str) -> ParseError {
+        ExprLagrangian::parse(src, &[("m", 1f64)]).unwrap_err()
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        // Unary minus binds looser than ^
+        assert_eq!(eval("-q^2", 3f64, 0f64, 0f64), -9f64);
+        assert_eq!(eval("(-q)^2", 3f64, 0f64, 0f64), 9f64);
+        // ^ takes a signed exponent and is right associative
+        assert_eq!(eval("2^-1", 0f64, 0f64, 0f64), 0.5);
+        assert_eq!(eval("2^3^2", 0f64, 0f64, 0f64), 512f64);
+        // - and / are left associative
+        assert_eq!(eval("a-b-c", 0f64, 0f64, 0f64), 0f64);
+        assert_eq!(eval("a/b/c", 0f64, 0f64, 0f64), 5f64 / 6f64);
+        assert_eq!(eval("a+b*c^2", 0f64, 0f64, 0f64), 17f64);
+        assert_eq!(eval("--q", 3f64, 0f64, 0f64), 3f64);
+        assert_eq!(eval("2*dq*t - 1e-1*q", 1f64, 2f64, 3f64), 11.9);
+    }
+
+    #[test]
+    fn constant_exponents_are_folded() {
+        let l = ExprLagrangian::parse("q^(a-3) + q^-(1+1)", &[("a", 5f64)]).unwrap();
+        match &l.expr {
+            Expr::Add(a, b) => {
+                assert!(matches!(a.as_ref(), Expr::Pow(_, n) if matches!(**n, Expr::Num(2f64))));
+                assert!(matches!(b.as_ref(), Expr::Pow(_, n) if matches!(**n, Expr::Num(-2f64))));
+            }
+            e => panic!("unexpected tree {e:?}"),
+        }
+        assert_eq!(l.calc_t(0f64, &2f64, &0f64), 4.25);
+    }
+
+    #[test]
+    fn parse_errors_report_positions() {
+        assert_eq!(error("q $ 2"), ParseError::UnexpectedChar(2, '$'));
+        assert_eq!(
+            error("q + * 2"),
+            ParseError::UnexpectedToken(4, "*".to_string())
+        );
+        assert_eq!(
+            error("(q))"),
+            ParseError::UnexpectedToken(3, ")".to_string())
+        );
+        assert_eq!(
+            error("1 + 1.2.3"),
+            ParseError::UnexpectedToken(4, "1.2.3".to_string())
+        );
+        assert_eq!(error("m*(q"), ParseError::UnexpectedEnd);
+        assert_eq!(error("q +"), ParseError::UnexpectedEnd);
+        assert_eq!(error("x*q"), ParseError::UnknownVariable("x".to_string()));
+        assert_eq!(
+            error("foo(q)"),
+            ParseError::UnknownFunction("foo".to_string())
+        );
+        assert!(error("x").to_string().contains("'x'"));
+    }
+
+    #[test]
+    fn parameter_errors() {
+        let parse = |params: &[(&str, f64)]| ExprLagrangian::parse("q", params).unwrap_err();
+        assert_eq!(
+            parse(&[("dq", 1f64)]),
+            ParseError::ReservedName("dq".to_string())
+        );
+        assert_eq!(
+            parse(&[("sin", 1f64)]),
+            ParseError::ReservedName("sin".to_string())
+        );
+        assert_eq!(
+            parse(&[("m", 1f64), ("m", 2f64)]),
+            ParseError::DuplicateParameter("m".to_string())
+        );
+
+        assert_eq!(
+            parse_params("m=1, g = 9.8").unwrap(),
+            vec![("m".to_string(), 1f64), ("g".to_string(), 9.8)]
+        );
+        assert_eq!(
+            parse_params("t=1"),
+            Err(ParseError::ReservedName("t".to_string()))
+        );
+        assert_eq!(
+            parse_params("cos=1"),
+            Err(ParseError::ReservedName("cos".to_string()))
+        );
+        assert_eq!(
+            parse_params("m=1,m=2"),
+            Err(ParseError::DuplicateParameter("m".to_string()))
+        );
+        for bad in ["m", "m=abc", "1m=2", "m n=1"] {
+            assert_eq!(
+                parse_params(bad),
+                Err(ParseError::InvalidParameter(bad.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn ad_matches_finite_differences() {
+        let params = [("m", 2f64), ("k", 3f64)];
+        let src = "0.5*m*dq^2 - k*q^2/2 + sin(q)*exp(-t) + cos(dq)/q^-2 + sqrt(1+dq^2)*q^t";
+        let l = ExprLagrangian::parse(src, &params).unwrap();
+        let l_fd = {
+            let l = l.clone();
+            FnLagrangian::new(move |t, q: &f64, dq: &f64| l.calc_t(t, q, dq))
+        };
+
+        let (t, q, dq) = (0.7, 0.4, -1.3);
+        let (ad_q, ad_dq) = derivative::gradient(&l, t, &q, &dq);
+        let (fd_q, fd_dq) = derivative::gradient(&l_fd, t, &q, &dq);
+        assert!((ad_q[0] - fd_q[0]).abs() < 1e-6);
+        assert!((ad_dq[0] - fd_dq[0]).abs() < 1e-6);
+
+        let ad_h = derivative::hessian(&l, t, &q, &dq);
+        let fd_h = derivative::hessian(&l_fd, t, &q, &dq);
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((ad_h[(i, j)] - fd_h[(i, j)]).abs() < 1e-4);
+            }
+        }
+    }
+}
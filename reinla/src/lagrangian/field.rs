@@ -0,0 +1,170 @@
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+use std::f64::consts::PI;
+
+/// Boundary condition at both ends of a chain or spatial grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Neighbours outside the chain are held at zero
+    Fixed,
+    /// Last site couples back to the first
+    Periodic,
+}
+
+/// Nearest-neighbour differences q_{i+1} - q_i including the boundary bonds; none for an
+/// empty field
+fn differences<T: Copy + std::ops::Sub<Output = T>>(
+    q: &[T],
+    zero: T,
+    boundary: Boundary,
+) -> Vec<T> {
+    let n = q.len();
+    if n == 0 {
+        return vec![];
+    }
+    match boundary {
+        Boundary::Fixed => {
+            let mut d = vec![q[0] - zero];
+            d.extend((0..n - 1).map(|i| q[i + 1] - q[i]));
+            d.push(zero - q[n - 1]);
+            d
+        }
+        Boundary::Periodic => (0..n).map(|i| q[(i + 1) % n] - q[i]).collect(),
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Oscillator Chain: L = Σ m q̇_i² / 2 - Σ k (q_{i+1} - q_i)² / 2
+// └──────────────────────────────────────────────────────────┘
+pub struct OscillatorChain {
+    mass: f64,
+    k: f64,
+    boundary: Boundary,
+}
+
+impl OscillatorChain {
+    pub fn new(mass: f64, k: f64, boundary: Boundary) -> Self {
+        Self { mass, k, boundary }
+    }
+
+    pub fn get_boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Angular frequency of the n-th normal mode of a chain with `sites` masses
+    pub fn normal_mode(&self, sites: usize, n: usize) -> f64 {
+        let w0 = (self.k / self.mass).sqrt();
+        match self.boundary {
+            Boundary::Fixed => 2f64 * w0 * (n as f64 * PI / (2 * (sites + 1)) as f64).sin(),
+            Boundary::Periodic => 2f64 * w0 * (n as f64 * PI / sites as f64).sin().abs(),
+        }
+    }
+}
+
+impl Lagrangian for OscillatorChain {
+    type Q = Vec<f64>;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        let kinetic = dq.iter().map(|v| v.powi(2)).sum::<f64>();
+        let elastic = differences(q, 0f64, self.boundary)
+            .iter()
+            .map(|d| d.powi(2))
+            .sum::<f64>();
+        0.5 * self.mass * kinetic - 0.5 * self.k * elastic
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        let kinetic = dq.iter().fold(AD0(0f64), |acc, v| acc + v.powi(2));
+        let elastic = differences(q, AD0(0f64), self.boundary)
+            .into_iter()
+            .fold(AD0(0f64), |acc, d| acc + d.powi(2));
+        Some(0.5 * self.mass * kinetic - 0.5 * self.k * elastic)
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  1+1D Scalar Field (Klein-Gordon / φ⁴)
+// └──────────────────────────────────────────────────────────┘
+/// Field sampled on a spatial grid with spacing dx
+///
+/// L = Σ dx [φ̇_i² / 2 - ((φ_{i+1} - φ_i) / dx)² / 2 - m² φ_i² / 2 - λ φ_i⁴ / 4!]
+pub struct ScalarField {
+    dx: f64,
+    mass: f64,
+    lambda: f64,
+    boundary: Boundary,
+}
+
+impl ScalarField {
+    pub fn klein_gordon(dx: f64, mass: f64, boundary: Boundary) -> Self {
+        Self::phi4(dx, mass, 0f64, boundary)
+    }
+
+    pub fn phi4(dx: f64, mass: f64, lambda: f64, boundary: Boundary) -> Self {
+        Self {
+            dx,
+            mass,
+            lambda,
+            boundary,
+        }
+    }
+
+    pub fn get_dx(&self) -> f64 {
+        self.dx
+    }
+
+    pub fn get_boundary(&self) -> Boundary {
+        self.boundary
+    }
+}
+
+impl Lagrangian for ScalarField {
+    type Q = Vec<f64>;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        let kinetic = dq.iter().map(|v| v.powi(2)).sum::<f64>();
+        let gradient = differences(q, 0f64, self.boundary)
+            .iter()
+            .map(|d| (d / self.dx).powi(2))
+            .sum::<f64>();
+        let potential = q
+            .iter()
+            .map(|x| 0.5 * self.mass.powi(2) * x.powi(2) + self.lambda / 24f64 * x.powi(4))
+            .sum::<f64>();
+        self.dx * (0.5 * kinetic - 0.5 * gradient - potential)
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        let kinetic = dq.iter().fold(AD0(0f64), |acc, v| acc + v.powi(2));
+        let gradient = differences(q, AD0(0f64), self.boundary)
+            .into_iter()
+            .fold(AD0(0f64), |acc, d| acc + (d / self.dx).powi(2));
+        let potential = q.iter().fold(AD0(0f64), |acc, x| {
+            acc + 0.5 * self.mass.powi(2) * x.powi(2) + self.lambda / 24f64 * x.powi(4)
+        });
+        Some(self.dx * (0.5 * kinetic - 0.5 * gradient - potential))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_differences_include_both_walls() {
+        let d = differences(&[1f64, 3f64], 0f64, Boundary::Fixed);
+        assert_eq!(d, vec![1f64, 2f64, -3f64]);
+        let d = differences(&[1f64, 3f64], 0f64, Boundary::Periodic);
+        assert_eq!(d, vec![2f64, -2f64]);
+    }
+
+    #[test]
+    fn empty_field_has_no_energy() {
+        for boundary in [Boundary::Fixed, Boundary::Periodic] {
+            let chain = OscillatorChain::new(1f64, 1f64, boundary);
+            assert_eq!(chain.calc(&vec![], &vec![]), 0f64);
+            let field = ScalarField::phi4(0.1, 1f64, 1f64, boundary);
+            assert_eq!(field.calc(&vec![], &vec![]), 0f64);
+        }
+    }
+}
@@ -1,8 +1,49 @@
+use crate::coordinate::Coordinate;
+use peroxide::fuga::{Matrix, AD};
+
 pub trait Lagrangian {
-    type Q;
+    type Q: Coordinate;
 
     fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64;
+
+    /// Lagrangian at time t; explicitly time-dependent Lagrangians override this and
+    /// evaluate `calc` at t = 0
+    fn calc_t(&self, _t: f64, q: &Self::Q, dq: &Self::Q) -> f64 {
+        self.calc(q, dq)
+    }
+
+    /// Whether (q, q̇) lies in the domain of L, e.g. subluminal velocities
+    fn is_admissible(&self, _q: &Self::Q, _dq: &Self::Q) -> bool {
+        true
+    }
+
+    /// Same as `calc_t` but on dual numbers; `None` falls back to finite differences
+    fn calc_ad(&self, _t: f64, _q: &[AD], _dq: &[AD]) -> Option<AD> {
+        None
+    }
+
+    /// ∂L/∂q
+    fn dl_dq(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> Self::Q {
+        let (dl_dq, _) = derivative::gradient(self, t, q, dq);
+        Self::Q::from_slice(&dl_dq)
+    }
+
+    /// ∂L/∂q̇
+    fn dl_ddq(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> Self::Q {
+        let (_, dl_ddq) = derivative::gradient(self, t, q, dq);
+        Self::Q::from_slice(&dl_ddq)
+    }
+
+    /// Hessian w.r.t. (q, q̇) ordered as [q_0, .., q_n, q̇_0, .., q̇_n]
+    fn hessian(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> Matrix {
+        derivative::hessian(self, t, q, dq)
+    }
 }
 
+pub mod derivative;
+pub mod em;
+pub mod expr;
+pub mod field;
+pub mod n_dim;
 pub mod one_dim;
 pub mod two_dim;
@@ -0,0 +1,157 @@
+use crate::coordinate::Coordinate;
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+use std::marker::PhantomData;
+
+fn norm_sq_ad(v: &[AD]) -> AD {
+    v.iter().fold(AD0(0f64), |acc, x| acc + x.powi(2))
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Free Body
+// └──────────────────────────────────────────────────────────┘
+pub struct FreeBody<Q> {
+    mass: f64,
+    _q: PhantomData<Q>,
+}
+
+impl<Q: Coordinate> FreeBody<Q> {
+    pub fn new(mass: f64) -> Self {
+        Self {
+            mass,
+            _q: PhantomData,
+        }
+    }
+}
+
+impl<Q: Coordinate> Lagrangian for FreeBody<Q> {
+    type Q = Q;
+
+    fn calc(&self, _q: &Self::Q, dq: &Self::Q) -> f64 {
+        0.5 * self.mass * dq.norm_sq()
+    }
+
+    fn calc_ad(&self, _t: f64, _q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(0.5 * self.mass * norm_sq_ad(dq))
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Uniform Gravity (along the last axis)
+// └──────────────────────────────────────────────────────────┘
+pub struct UniformGravity<Q> {
+    mass: f64,
+    g: f64,
+    _q: PhantomData<Q>,
+}
+
+impl<Q: Coordinate> UniformGravity<Q> {
+    pub fn new(mass: f64, g: f64) -> Self {
+        Self {
+            mass,
+            g,
+            _q: PhantomData,
+        }
+    }
+}
+
+impl<Q: Coordinate> Lagrangian for UniformGravity<Q> {
+    type Q = Q;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        0.5 * self.mass * dq.norm_sq() - self.mass * self.g * q.get(q.dim() - 1)
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(0.5 * self.mass * norm_sq_ad(dq) - self.mass * self.g * q[q.len() - 1])
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Isotropic Simple Harmonic Oscillator
+// └──────────────────────────────────────────────────────────┘
+pub struct SHO<Q> {
+    mass: f64,
+    k: f64,
+    _q: PhantomData<Q>,
+}
+
+impl<Q: Coordinate> SHO<Q> {
+    pub fn new(mass: f64, k: f64) -> Self {
+        Self {
+            mass,
+            k,
+            _q: PhantomData,
+        }
+    }
+}
+
+impl<Q: Coordinate> Lagrangian for SHO<Q> {
+    type Q = Q;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        0.5 * self.mass * dq.norm_sq() - 0.5 * self.k * q.norm_sq()
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(0.5 * self.mass * norm_sq_ad(dq) - 0.5 * self.k * norm_sq_ad(q))
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Relativistic Particle: L = -m c² √(1 - v²/c²) - V(q)
+// └──────────────────────────────────────────────────────────┘
+pub type PotentialFn<Q> = Box<dyn Fn(&Q) -> f64>;
+
+pub struct RelativisticParticle<Q> {
+    mass: f64,
+    c: f64,
+    potential: Option<PotentialFn<Q>>,
+}
+
+impl<Q: Coordinate> RelativisticParticle<Q> {
+    pub fn new(mass: f64, c: f64) -> Self {
+        Self {
+            mass,
+            c,
+            potential: None,
+        }
+    }
+
+    /// Free particle moving in the potential V(q)
+    pub fn with_potential<F: Fn(&Q) -> f64 + 'static>(mass: f64, c: f64, potential: F) -> Self {
+        Self {
+            mass,
+            c,
+            potential: Some(Box::new(potential)),
+        }
+    }
+
+    pub fn get_c(&self) -> f64 {
+        self.c
+    }
+}
+
+impl<Q: Coordinate> Lagrangian for RelativisticParticle<Q> {
+    type Q = Q;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        let v = match &self.potential {
+            Some(potential) => potential(q),
+            None => 0f64,
+        };
+        -self.mass * self.c.powi(2) * (1f64 - dq.norm_sq() / self.c.powi(2)).sqrt() - v
+    }
+
+    fn is_admissible(&self, _q: &Self::Q, dq: &Self::Q) -> bool {
+        dq.norm_sq() < self.c.powi(2)
+    }
+
+    fn calc_ad(&self, _t: f64, _q: &[AD], dq: &[AD]) -> Option<AD> {
+        if self.potential.is_some() {
+            return None;
+        }
+        let c2 = self.c.powi(2);
+        Some(-self.mass * c2 * (1f64 - norm_sq_ad(dq) / c2).sqrt())
+    }
+}
@@ -1,66 +1,149 @@
-use crate::lagrangian::Lagrangian;
+use crate::lagrangian::{derivative, n_dim, Lagrangian};
+use crate::potential::Potential;
+use peroxide::fuga::*;
 
 // ┌──────────────────────────────────────────────────────────┐
-//  Free Body
+//  Lagrangians shared with n_dim
 // └──────────────────────────────────────────────────────────┘
-pub struct FreeBody {
+pub type FreeBody = n_dim::FreeBody<f64>;
+pub type UniformGravity = n_dim::UniformGravity<f64>;
+pub type SHO = n_dim::SHO<f64>;
+pub type RelativisticParticle = n_dim::RelativisticParticle<f64>;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Driven Harmonic Oscillator
+// └──────────────────────────────────────────────────────────┘
+pub struct DrivenOscillator {
     mass: f64,
+    k: f64,
+    force: f64,
+    omega: f64,
 }
 
-impl FreeBody {
-    pub fn new(mass: f64) -> Self {
-        Self { mass }
+impl DrivenOscillator {
+    /// External force F(t) = force * cos(omega * t)
+    pub fn new(mass: f64, k: f64, force: f64, omega: f64) -> Self {
+        Self {
+            mass,
+            k,
+            force,
+            omega,
+        }
     }
 }
 
-impl Lagrangian for FreeBody {
+impl Lagrangian for DrivenOscillator {
     type Q = f64;
 
-    fn calc(&self, _q: &Self::Q, dq: &Self::Q) -> f64 {
-        0.5 * self.mass * dq.powi(2)
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        self.calc_t(0f64, q, dq)
+    }
+
+    fn calc_t(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> f64 {
+        0.5 * self.mass * dq.powi(2) - 0.5 * self.k * q.powi(2)
+            + self.force * (self.omega * t).cos() * q
+    }
+
+    fn calc_ad(&self, t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(
+            0.5 * self.mass * dq[0].powi(2) - 0.5 * self.k * q[0].powi(2)
+                + self.force * (self.omega * t).cos() * q[0],
+        )
     }
 }
 
 // ┌──────────────────────────────────────────────────────────┐
-//  Uniform Gravity
+//  Caldirola-Kanai (damped harmonic oscillator)
 // └──────────────────────────────────────────────────────────┘
-pub struct UniformGravity {
+pub struct CaldirolaKanai {
     mass: f64,
+    k: f64,
+    gamma: f64,
+}
+
+impl CaldirolaKanai {
+    pub fn new(mass: f64, k: f64, gamma: f64) -> Self {
+        Self { mass, k, gamma }
+    }
+}
+
+impl Lagrangian for CaldirolaKanai {
+    type Q = f64;
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        self.calc_t(0f64, q, dq)
+    }
+
+    fn calc_t(&self, t: f64, q: &Self::Q, dq: &Self::Q) -> f64 {
+        (self.gamma * t).exp() * (0.5 * self.mass * dq.powi(2) - 0.5 * self.k * q.powi(2))
+    }
+
+    fn calc_ad(&self, t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(
+            (self.gamma * t).exp()
+                * (0.5 * self.mass * dq[0].powi(2) - 0.5 * self.k * q[0].powi(2)),
+        )
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Simple Pendulum (q = angle from the downward vertical)
+// └──────────────────────────────────────────────────────────┘
+pub struct Pendulum {
+    mass: f64,
+    length: f64,
     g: f64,
 }
 
-impl UniformGravity {
-    pub fn new(mass: f64, g: f64) -> Self {
-        Self { mass, g }
+impl Pendulum {
+    pub fn new(mass: f64, length: f64, g: f64) -> Self {
+        Self { mass, length, g }
     }
 }
 
-impl Lagrangian for UniformGravity {
+impl Lagrangian for Pendulum {
     type Q = f64;
 
     fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
-        0.5 * self.mass * dq.powi(2) - self.mass * self.g * q // y = -q
+        0.5 * self.mass * self.length.powi(2) * dq.powi(2)
+            + self.mass * self.g * self.length * q.cos()
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(
+            0.5 * self.mass * self.length.powi(2) * dq[0].powi(2)
+                + self.mass * self.g * self.length * derivative::cos(q[0]),
+        )
     }
 }
 
 // ┌──────────────────────────────────────────────────────────┐
-//  Simple Harmonic Oscillator
+//  Conservative System: L = m q̇² / 2 - V(q)
 // └──────────────────────────────────────────────────────────┘
-pub struct SHO {
+pub struct Conservative<P: Potential> {
     mass: f64,
-    k: f64,
+    potential: P,
 }
 
-impl SHO {
-    pub fn new(mass: f64, k: f64) -> Self {
-        Self { mass, k }
+impl<P: Potential> Conservative<P> {
+    pub fn new(mass: f64, potential: P) -> Self {
+        Self { mass, potential }
+    }
+
+    pub fn get_potential(&self) -> &P {
+        &self.potential
     }
 }
 
-impl Lagrangian for SHO {
+impl<P: Potential> Lagrangian for Conservative<P> {
     type Q = f64;
 
     fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
-        0.5 * self.mass * dq.powi(2) - 0.5 * self.k * q.powi(2)
+        0.5 * self.mass * dq.powi(2) - self.potential.eval(*q)
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        let v = self.potential.eval_ad(q[0])?;
+        Some(0.5 * self.mass * dq[0].powi(2) - v)
     }
 }
@@ -1,44 +1,135 @@
-use crate::lagrangian::Lagrangian;
+use crate::lagrangian::{derivative, n_dim, Lagrangian};
+use peroxide::fuga::*;
 
 // ┌──────────────────────────────────────────────────────────┐
-//  Free Body
+//  Free Body & Uniform Gravity
 // └──────────────────────────────────────────────────────────┘
-pub struct FreeBody {
+pub type FreeBody = n_dim::FreeBody<(f64, f64)>;
+pub type UniformGravity = n_dim::UniformGravity<(f64, f64)>;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Double Pendulum (q = angles of both rods from the downward vertical)
+// └──────────────────────────────────────────────────────────┘
+pub struct DoublePendulum {
+    m1: f64,
+    m2: f64,
+    l1: f64,
+    l2: f64,
+    g: f64,
+}
+
+impl DoublePendulum {
+    pub fn new(m1: f64, m2: f64, l1: f64, l2: f64, g: f64) -> Self {
+        Self { m1, m2, l1, l2, g }
+    }
+}
+
+impl Lagrangian for DoublePendulum {
+    type Q = (f64, f64);
+
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        let (m1, m2, l1, l2, g) = (self.m1, self.m2, self.l1, self.l2, self.g);
+        let t = 0.5 * (m1 + m2) * l1.powi(2) * dq.0.powi(2)
+            + 0.5 * m2 * l2.powi(2) * dq.1.powi(2)
+            + m2 * l1 * l2 * dq.0 * dq.1 * (q.0 - q.1).cos();
+        let v = -(m1 + m2) * g * l1 * q.0.cos() - m2 * g * l2 * q.1.cos();
+        t - v
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        let (m1, m2, l1, l2, g) = (self.m1, self.m2, self.l1, self.l2, self.g);
+        let t = 0.5 * (m1 + m2) * l1.powi(2) * dq[0].powi(2)
+            + 0.5 * m2 * l2.powi(2) * dq[1].powi(2)
+            + m2 * l1 * l2 * dq[0] * dq[1] * derivative::cos(q[0] - q[1]);
+        let v = -(m1 + m2) * g * l1 * derivative::cos(q[0]) - m2 * g * l2 * derivative::cos(q[1]);
+        Some(t - v)
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Central Potentials V(r)
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone, Copy)]
+pub enum CentralPotential {
+    /// V = -k / r
+    Kepler { k: f64 },
+    /// V = k r² / 2
+    Harmonic { k: f64 },
+    /// V = k rⁿ
+    PowerLaw { k: f64, n: f64 },
+}
+
+impl CentralPotential {
+    pub fn eval(&self, r: f64) -> f64 {
+        match *self {
+            CentralPotential::Kepler { k } => -k / r,
+            CentralPotential::Harmonic { k } => 0.5 * k * r.powi(2),
+            CentralPotential::PowerLaw { k, n } => k * r.powf(n),
+        }
+    }
+
+    pub fn eval_ad(&self, r: AD) -> AD {
+        match *self {
+            CentralPotential::Kepler { k } => -k / r,
+            CentralPotential::Harmonic { k } => 0.5 * k * r.powi(2),
+            CentralPotential::PowerLaw { k, n } => k * r.powf(n),
+        }
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Central Force (Cartesian, q = (x, y))
+// └──────────────────────────────────────────────────────────┘
+pub struct CentralForce {
     mass: f64,
+    potential: CentralPotential,
 }
 
-impl FreeBody {
-    pub fn new(mass: f64) -> Self {
-        Self { mass }
+impl CentralForce {
+    pub fn new(mass: f64, potential: CentralPotential) -> Self {
+        Self { mass, potential }
     }
 }
 
-impl Lagrangian for FreeBody {
+impl Lagrangian for CentralForce {
     type Q = (f64, f64);
 
-    fn calc(&self, _q: &Self::Q, dq: &Self::Q) -> f64 {
-        0.5 * self.mass * (dq.0.powi(2) + dq.1.powi(2))
+    fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
+        let r = (q.0.powi(2) + q.1.powi(2)).sqrt();
+        0.5 * self.mass * (dq.0.powi(2) + dq.1.powi(2)) - self.potential.eval(r)
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        let r = (q[0].powi(2) + q[1].powi(2)).sqrt();
+        Some(0.5 * self.mass * (dq[0].powi(2) + dq[1].powi(2)) - self.potential.eval_ad(r))
     }
 }
 
 // ┌──────────────────────────────────────────────────────────┐
-//  Uniform Gravity
+//  Central Force (Polar, q = (r, θ))
 // └──────────────────────────────────────────────────────────┘
-pub struct UniformGravity {
+pub struct CentralForcePolar {
     mass: f64,
-    g: f64,
+    potential: CentralPotential,
 }
 
-impl UniformGravity {
-    pub fn new(mass: f64, g: f64) -> Self {
-        Self { mass, g }
+impl CentralForcePolar {
+    pub fn new(mass: f64, potential: CentralPotential) -> Self {
+        Self { mass, potential }
     }
 }
 
-impl Lagrangian for UniformGravity {
+impl Lagrangian for CentralForcePolar {
     type Q = (f64, f64);
 
     fn calc(&self, q: &Self::Q, dq: &Self::Q) -> f64 {
-        0.5 * self.mass * (dq.0.powi(2) + dq.1.powi(2)) - self.mass * self.g * q.1
+        0.5 * self.mass * (dq.0.powi(2) + q.0.powi(2) * dq.1.powi(2)) - self.potential.eval(q.0)
+    }
+
+    fn calc_ad(&self, _t: f64, q: &[AD], dq: &[AD]) -> Option<AD> {
+        Some(
+            0.5 * self.mass * (dq[0].powi(2) + q[0].powi(2) * dq[1].powi(2))
+                - self.potential.eval_ad(q[0]),
+        )
     }
 }
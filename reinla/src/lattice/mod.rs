@@ -1,2 +1,92 @@
+use std::f64::consts::PI;
+
+/// Mapping from integer node index to a physical coordinate along one axis
+///
+/// Periodic axes (angles) measure displacements the short way around
+#[derive(Debug, Clone, Copy)]
+pub struct Axis {
+    origin: f64,
+    spacing: f64,
+    period: Option<f64>,
+}
+
+impl Axis {
+    pub fn linear(origin: f64, spacing: f64) -> Self {
+        Self {
+            origin,
+            spacing,
+            period: None,
+        }
+    }
+
+    /// `num_nodes` equally spaced angles in [-π, π)
+    pub fn angular(num_nodes: usize) -> Self {
+        Self {
+            origin: -PI,
+            spacing: 2f64 * PI / num_nodes as f64,
+            period: Some(2f64 * PI),
+        }
+    }
+
+    pub fn spacing(&self) -> f64 {
+        self.spacing
+    }
+
+    pub fn period(&self) -> Option<f64> {
+        self.period
+    }
+
+    pub fn position(&self, node: i64) -> f64 {
+        let x = self.origin + self.spacing * node as f64;
+        match self.period {
+            Some(p) => self.origin + (x - self.origin).rem_euclid(p),
+            None => x,
+        }
+    }
+
+    /// Nearest node to a coordinate
+    pub fn node(&self, x: f64) -> i64 {
+        let i = ((x - self.origin) / self.spacing).round() as i64;
+        match self.period {
+            Some(p) => i.rem_euclid((p / self.spacing).round() as i64),
+            None => i,
+        }
+    }
+
+    /// Image of `x` closest to `reference`, so periodic coordinates stay continuous
+    pub fn nearest(&self, reference: f64, x: f64) -> f64 {
+        match self.period {
+            Some(p) => reference + (x - reference + 0.5 * p).rem_euclid(p) - 0.5 * p,
+            None => x,
+        }
+    }
+
+    pub fn displacement(&self, from: i64, to: i64) -> f64 {
+        let d = self.spacing * (to - from) as f64;
+        match self.period {
+            Some(p) => (d + 0.5 * p).rem_euclid(p) - 0.5 * p,
+            None => d,
+        }
+    }
+}
+
+/// Criterion used by lattice brute force searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    /// Smallest action
+    #[default]
+    Minimal,
+    /// Smallest squared discrete Euler-Lagrange residual, so saddle points are found too
+    Stationary,
+}
+
+impl Default for Axis {
+    fn default() -> Self {
+        Self::linear(0f64, 1f64)
+    }
+}
+
+pub mod field;
+pub mod n_dim;
 pub mod one_dim;
 pub mod two_dim;
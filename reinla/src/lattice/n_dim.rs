@@ -0,0 +1,310 @@
+use crate::coordinate::Coordinate;
+use crate::discrete::DiscreteLagrangian;
+use crate::lagrangian::Lagrangian;
+use crate::lattice::{Axis, SearchMode};
+use crate::stationary::StationarySolver;
+use crate::verify::{verify, verify_with_solution, Verdict};
+
+pub type Node = Vec<i64>;
+
+/// Integer lattice over any number of degrees of freedom
+///
+/// Each interior time step may visit any node inside `bounds` (inclusive per axis);
+/// node indices are mapped to coordinates through one `Axis` per degree of freedom
+#[derive(Debug)]
+pub struct LatticeND<L: Lagrangian> {
+    init_node: Node,
+    end_node: Node,
+    bounds: Vec<(i64, i64)>,
+    axes: Vec<Axis>,
+    dt: f64,
+    search_mode: SearchMode,
+    monotone: bool,
+    t: usize,
+    lagrangian: L,
+}
+
+impl<L: Lagrangian> LatticeND<L> {
+    pub fn new(
+        init_node: Node,
+        end_node: Node,
+        bounds: Vec<(i64, i64)>,
+        t: usize,
+        lagrangian: L,
+    ) -> Self {
+        assert!(t >= 1, "a lattice path needs at least one time step");
+        assert_eq!(init_node.len(), bounds.len());
+        assert_eq!(end_node.len(), bounds.len());
+        let axes = vec![Axis::default(); bounds.len()];
+        Self {
+            init_node,
+            end_node,
+            bounds,
+            axes,
+            dt: 1f64,
+            search_mode: SearchMode::default(),
+            monotone: false,
+            t,
+            lagrangian,
+        }
+    }
+
+    pub fn set_axes(&mut self, axes: Vec<Axis>) {
+        assert_eq!(axes.len(), self.bounds.len());
+        self.axes = axes;
+    }
+
+    pub fn get_axes(&self) -> &[Axis] {
+        &self.axes
+    }
+
+    /// Physical duration of one time step
+    pub fn set_dt(&mut self, dt: f64) {
+        self.dt = dt;
+    }
+
+    pub fn get_dt(&self) -> f64 {
+        self.dt
+    }
+
+    pub fn set_search_mode(&mut self, search_mode: SearchMode) {
+        self.search_mode = search_mode;
+    }
+
+    pub fn get_search_mode(&self) -> SearchMode {
+        self.search_mode
+    }
+
+    /// Restrict `brute_force` to interior nodes that strictly increase along every axis
+    pub fn set_monotone(&mut self, monotone: bool) {
+        self.monotone = monotone;
+    }
+
+    pub fn get_monotone(&self) -> bool {
+        self.monotone
+    }
+
+    pub fn get_bounds(&self) -> &[(i64, i64)] {
+        &self.bounds
+    }
+
+    pub fn set_bounds(&mut self, bounds: Vec<(i64, i64)>) {
+        assert_eq!(bounds.len(), self.bounds.len());
+        self.bounds = bounds;
+    }
+
+    pub fn lagrangian(&self) -> &L {
+        &self.lagrangian
+    }
+
+    pub fn get_init_node(&self) -> &Node {
+        &self.init_node
+    }
+
+    pub fn get_end_node(&self) -> &Node {
+        &self.end_node
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.t
+    }
+
+    pub fn dim(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Physical time at the midpoint of the k-th step
+    pub fn t_mid(&self, k: usize) -> f64 {
+        (k as f64 + 0.5) * self.dt
+    }
+
+    pub fn position(&self, node: &[i64]) -> L::Q {
+        let q = node
+            .iter()
+            .zip(self.axes.iter())
+            .map(|(&x, axis)| axis.position(x))
+            .collect::<Vec<f64>>();
+        L::Q::from_slice(&q)
+    }
+
+    /// End points of the segment between two nodes, unwrapped along periodic axes
+    pub fn segment(&self, from: &[i64], to: &[i64]) -> (L::Q, L::Q) {
+        let q0 = self.position(from);
+        let q1 = (0..self.dim())
+            .map(|i| q0.get(i) + self.axes[i].displacement(from[i], to[i]))
+            .collect::<Vec<f64>>();
+        (q0, L::Q::from_slice(&q1))
+    }
+
+    /// Whether the segment between two nodes stays inside the domain of L
+    pub fn is_admissible(&self, from: &[i64], to: &[i64]) -> bool {
+        let (q0, q1) = self.segment(from, to);
+        DiscreteLagrangian::new(&self.lagrangian, self.dt).is_admissible(&q0, &q1)
+    }
+
+    /// Physical coordinates of a node path, unwrapped along periodic axes
+    pub fn positions(&self, path: &[Node]) -> Vec<L::Q> {
+        let mut q = vec![self.position(&path[0])];
+        for w in path.windows(2) {
+            let q_prev = q[q.len() - 1].to_vec();
+            let q_next = (0..self.dim())
+                .map(|i| q_prev[i] + self.axes[i].displacement(w[0][i], w[1][i]))
+                .collect::<Vec<f64>>();
+            q.push(L::Q::from_slice(&q_next));
+        }
+        q
+    }
+
+    /// Every node inside the bounds
+    pub fn nodes(&self) -> Vec<Node> {
+        let mut nodes = vec![vec![]];
+        for &(lo, hi) in self.bounds.iter() {
+            nodes = nodes
+                .into_iter()
+                .flat_map(|node| {
+                    (lo..=hi).map(move |x| {
+                        let mut node = node.clone();
+                        node.push(x);
+                        node
+                    })
+                })
+                .collect();
+        }
+        nodes
+    }
+
+    /// Action of a full path (both ends included)
+    pub fn action(&self, path: &[Node]) -> f64 {
+        DiscreteLagrangian::new(&self.lagrangian, self.dt).action(&self.positions(path))
+    }
+
+    /// Quantity minimized by `brute_force` under the current search mode
+    pub fn objective(&self, path: &[Node]) -> f64 {
+        match self.search_mode {
+            SearchMode::Minimal => self.action(path),
+            SearchMode::Stationary => {
+                StationarySolver::new(&self.lagrangian, self.dt).residual_sq(&self.positions(path))
+            }
+        }
+    }
+
+    /// Advance `index` to the next interior node sequence; false once every one was visited
+    ///
+    /// Monotone lattices walk the strictly increasing index sequences, which contain every
+    /// path increasing along all axes since `nodes` is in lexicographic order
+    fn next_index(&self, index: &mut [usize], n: usize) -> bool {
+        let len = index.len();
+        if self.monotone {
+            let Some(k) = (0..len).rev().find(|&k| index[k] + len - k < n) else {
+                return false;
+            };
+            index[k] += 1;
+            for j in k + 1..len {
+                index[j] = index[j - 1] + 1;
+            }
+        } else {
+            // Odometer over interior time steps
+            let mut k = 0;
+            while k < len && index[k] + 1 == n {
+                index[k] = 0;
+                k += 1;
+            }
+            if k == len {
+                return false;
+            }
+            index[k] += 1;
+        }
+        true
+    }
+
+    fn is_increasing(&self, path: &[&Node]) -> bool {
+        path.windows(2)
+            .all(|w| w[0].iter().zip(w[1].iter()).all(|(a, b)| a < b))
+    }
+
+    /// Path with the smallest objective, or `None` if every path leaves the domain of L
+    pub fn brute_force(&self) -> Option<Vec<Node>> {
+        let nodes = self.nodes();
+        let discrete = DiscreteLagrangian::new(&self.lagrangian, self.dt);
+        let len = self.t - 1;
+
+        let mut index = if self.monotone {
+            (0..len).collect::<Vec<usize>>()
+        } else {
+            vec![0usize; len]
+        };
+        let mut min_val = f64::INFINITY;
+        let mut min_index = None;
+        let mut searching = !self.monotone || len <= nodes.len();
+
+        while searching {
+            let interior = index.iter().map(|&i| &nodes[i]).collect::<Vec<_>>();
+            if self.monotone && !self.is_increasing(&interior) {
+                searching = self.next_index(&mut index, nodes.len());
+                continue;
+            }
+
+            // Paths leaving the domain of L are rejected
+            let mut action = 0f64;
+            let mut prev = &self.init_node;
+            let steps = interior
+                .iter()
+                .copied()
+                .chain(std::iter::once(&self.end_node));
+            for (k, next) in steps.enumerate() {
+                let (q0, q1) = self.segment(prev, next);
+                if !discrete.is_admissible(&q0, &q1) {
+                    action = f64::INFINITY;
+                    break;
+                }
+                action += discrete.calc(discrete.t_mid(k), &q0, &q1);
+                prev = next;
+            }
+            if self.search_mode == SearchMode::Stationary && action.is_finite() {
+                let mut path = vec![self.init_node.clone()];
+                path.extend(interior.into_iter().cloned());
+                path.push(self.end_node.clone());
+                action = self.objective(&path);
+            }
+
+            if action < min_val {
+                min_val = action;
+                min_index = Some(index.clone());
+            }
+
+            searching = self.next_index(&mut index, nodes.len());
+        }
+
+        let mut path = vec![self.init_node.clone()];
+        path.extend(min_index?.into_iter().map(|i| nodes[i].clone()));
+        path.push(self.end_node.clone());
+        Some(path)
+    }
+
+    pub fn verify(&self, path: &[Node], residual_tol: f64) -> Verdict {
+        verify(
+            &self.lagrangian,
+            &self.positions(path),
+            self.dt,
+            residual_tol,
+        )
+    }
+
+    /// Same as `verify`, also comparing with the continuum solution
+    pub fn verify_with_solution<F: Fn(f64) -> L::Q>(
+        &self,
+        path: &[Node],
+        residual_tol: f64,
+        deviation_tol: f64,
+        exact: F,
+    ) -> Verdict {
+        verify_with_solution(
+            &self.lagrangian,
+            &self.positions(path),
+            self.dt,
+            residual_tol,
+            deviation_tol,
+            exact,
+        )
+    }
+}
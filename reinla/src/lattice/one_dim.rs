@@ -1,16 +1,20 @@
 use crate::lagrangian::Lagrangian;
-use crate::util::comb;
+use crate::lattice::n_dim::{LatticeND, Node};
+use crate::lattice::{Axis, SearchMode};
+use crate::verify::Verdict;
 use forger::env::Env;
 
 type S = (usize, i64);
 
+/// One degree of freedom on `LatticeND`, with the reinforcement learning environment on top
+///
+/// On a linear axis `brute_force` only visits interior nodes in 1..num_nodes - 1 that strictly
+/// increase; on a periodic axis it visits every sequence of the num_nodes angles, so paths
+/// may wrap around and wind
 #[derive(Debug)]
 pub struct Lattice1D<L: Lagrangian> {
+    lattice: LatticeND<L>,
     num_nodes: usize,
-    init_node: i64,
-    end_node: i64,
-    t: usize,
-    lagrangian: L,
     _l_min_max: Option<(f64, f64)>,
 }
 
@@ -22,31 +26,114 @@ impl<L: Lagrangian<Q = f64>> Lattice1D<L> {
         t: usize,
         lagrangian: L,
     ) -> Lattice1D<L> {
-        Lattice1D {
-            num_nodes,
-            init_node,
-            end_node,
+        let mut lattice = LatticeND::new(
+            vec![init_node],
+            vec![end_node],
+            vec![(1, num_nodes as i64 - 2)],
             t,
             lagrangian,
+        );
+        lattice.set_monotone(true);
+        Lattice1D {
+            lattice,
+            num_nodes,
             _l_min_max: None,
         }
     }
 
+    /// Underlying N-D lattice with a single axis
+    pub fn get_lattice(&self) -> &LatticeND<L> {
+        &self.lattice
+    }
+
+    pub fn set_axis(&mut self, axis: Axis) {
+        let n = self.num_nodes as i64;
+        match axis.period() {
+            Some(p) => {
+                assert_eq!(
+                    (p / axis.spacing()).round() as i64,
+                    n,
+                    "periodic axis must have num_nodes nodes"
+                );
+                self.lattice.set_bounds(vec![(0, n - 1)]);
+                self.lattice.set_monotone(false);
+            }
+            None => {
+                self.lattice.set_bounds(vec![(1, n - 2)]);
+                self.lattice.set_monotone(true);
+            }
+        }
+        self.lattice.set_axes(vec![axis]);
+    }
+
+    pub fn get_axis(&self) -> Axis {
+        self.lattice.get_axes()[0]
+    }
+
+    /// Physical duration of one time step
+    pub fn set_dt(&mut self, dt: f64) {
+        self.lattice.set_dt(dt);
+    }
+
+    pub fn get_dt(&self) -> f64 {
+        self.lattice.get_dt()
+    }
+
+    pub fn set_search_mode(&mut self, search_mode: SearchMode) {
+        self.lattice.set_search_mode(search_mode);
+    }
+
+    pub fn get_search_mode(&self) -> SearchMode {
+        self.lattice.get_search_mode()
+    }
+
+    /// (midpoint, velocity) of the segment between two nodes
+    pub fn segment(&self, from: i64, to: i64) -> (f64, f64) {
+        let (q0, q1) = self.lattice.segment(&[from], &[to]);
+        (0.5 * (q0 + q1), (q1 - q0) / self.get_dt())
+    }
+
+    /// Whether the segment between two nodes stays inside the domain of L
+    pub fn is_admissible(&self, from: i64, to: i64) -> bool {
+        self.lattice.is_admissible(&[from], &[to])
+    }
+
+    /// Physical time at the midpoint of the k-th step
+    pub fn t_mid(&self, k: usize) -> f64 {
+        self.lattice.t_mid(k)
+    }
+
+    /// Physical coordinates of a node path, unwrapped along periodic axes
+    pub fn positions(&self, path: &[i64]) -> Vec<f64> {
+        self.lattice.positions(&nodes(path))
+    }
+
+    /// Action of a full node path (both ends included)
+    pub fn action(&self, path: &[i64]) -> f64 {
+        self.lattice.action(&nodes(path))
+    }
+
+    /// Quantity minimized by `brute_force` under the current search mode
+    pub fn objective(&self, path: &[i64]) -> f64 {
+        self.lattice.objective(&nodes(path))
+    }
+
+    /// Lagrangian of the segment whose midpoint is at time t
     #[allow(non_snake_case)]
-    pub fn L(&self, q: f64, dq: f64) -> f64 {
-        self.lagrangian.calc(&q, &dq)
+    pub fn L(&self, t: f64, q: f64, dq: f64) -> f64 {
+        self.lattice.lagrangian().calc_t(t, &q, &dq)
     }
 
     pub fn get_init_node(&self) -> i64 {
-        self.init_node
+        self.lattice.get_init_node()[0]
     }
 
     pub fn get_end_node(&self) -> i64 {
-        self.end_node
+        self.lattice.get_end_node()[0]
     }
 
     pub fn get_t(&self) -> usize {
-        self.t
+        self.lattice.get_t()
     }
 
     pub fn get_num_nodes(&self) -> usize {
@@ -61,8 +148,8 @@ impl<L: Lagrangian<Q = f64>> Lattice1D<L> {
         self._l_min_max = None;
     }
 
-    pub fn reward(&self, q: f64, dq: f64) -> f64 {
-        let l = self.L(q, dq);
+    pub fn reward(&self, t: f64, q: f64, dq: f64) -> f64 {
+        let l = self.L(t, q, dq);
         let c = self._l_min_max;
         match c {
             Some((l_min, l_max)) => {
@@ -73,57 +160,50 @@ impl<L: Lagrangian<Q = f64>> Lattice1D<L> {
         }
     }
 
-    pub fn brute_force(&self) -> Vec<i64> {
-        let mut min_val = std::f64::MAX;
-        let mut min_path = vec![0i64; self.t + 1];
-
-        min_path[0] = self.init_node;
-        min_path[self.t] = self.end_node;
-
-        for q_vec in comb(self.num_nodes as i64 - 2, self.t - 1).into_iter() {
-            let mut action = 0f64;
-
-            let v0 = (q_vec[0] - self.init_node) as f64;
-            let x0 = (q_vec[0] + self.init_node) as f64 / 2f64;
-            action += self.L(x0, v0);
+    /// Path with the smallest objective, or `None` if every path leaves the domain of L
+    pub fn brute_force(&self) -> Option<Vec<i64>> {
+        let path = self.lattice.brute_force()?;
+        Some(path.into_iter().map(|node| node[0]).collect())
+    }
 
-            for i in (1..self.t - 1).rev() {
-                let vi = (q_vec[i] - q_vec[i - 1]) as f64;
-                let xi = (q_vec[i] + q_vec[i - 1]) as f64 / 2f64;
-                action += self.L(xi, vi);
-            }
-            let qm = q_vec[self.t - 2];
-            let vm = (self.end_node - qm) as f64;
-            let xm = (self.end_node + qm) as f64 / 2f64;
-            action += self.L(xm, vm);
-
-            if action < min_val {
-                for j in 1..self.t {
-                    min_path[j] = q_vec[j - 1];
-                }
-                min_val = action;
-            }
-        }
+    /// Check a node path (including both ends) against the discrete equations of motion
+    pub fn verify(&self, path: &[i64], residual_tol: f64) -> Verdict {
+        self.lattice.verify(&nodes(path), residual_tol)
+    }
 
-        min_path
+    pub fn verify_with_solution<F: Fn(f64) -> f64>(
+        &self,
+        path: &[i64],
+        residual_tol: f64,
+        deviation_tol: f64,
+        exact: F,
+    ) -> Verdict {
+        self.lattice
+            .verify_with_solution(&nodes(path), residual_tol, deviation_tol, exact)
     }
 }
 
+fn nodes(path: &[i64]) -> Vec<Node> {
+    path.iter().map(|&x| vec![x]).collect()
+}
+
 impl<L: Lagrangian<Q = f64>> Env<S, i64> for Lattice1D<L> {
     fn is_terminal(&self, state: &S) -> bool {
-        state.0 >= self.t
+        state.0 >= self.get_t()
     }
 
     fn is_goal(&self, state: &S) -> bool {
-        state.1 == self.end_node
+        state.1 == self.get_end_node()
     }
 
     fn transition(&self, state: &S, action: &Option<i64>) -> (Option<S>, f64) {
         if self.is_terminal(state) {
-            let q = (state.1 + self.end_node) as f64 / 2f64;
-            let dq = (self.end_node - state.1) as f64;
+            if !self.is_admissible(state.1, self.get_end_node()) {
+                return (None, f64::INFINITY);
+            }
+            let (q, dq) = self.segment(state.1, self.get_end_node());
 
-            let reward = self.reward(q, dq);
+            let reward = self.reward(self.t_mid(state.0), q, dq);
 
             return (None, reward);
         }
@@ -132,19 +212,87 @@ impl<L: Lagrangian<Q = f64>> Env<S, i64> for Lattice1D<L> {
         let q_curr = state.1;
         let q_next = *action;
 
-        let q = (q_curr + q_next) as f64 / 2f64;
-        let dq = (q_next - q_curr) as f64;
+        let (q, dq) = self.segment(q_curr, q_next);
 
-        let reward = self.reward(q, dq);
+        let reward = self.reward(self.t_mid(state.0), q, dq);
 
         (Some((state.0 + 1, q_next)), reward)
     }
 
     fn available_actions(&self, state: &S) -> Vec<i64> {
         if self.is_terminal(state) {
-            return vec![self.end_node];
+            return vec![self.get_end_node()];
         }
-        // Monotonic increasing
-        (state.1..self.end_node).collect()
+        // Monotonic increasing, or anywhere around a periodic axis
+        let nodes = match self.get_axis().period() {
+            Some(_) => 0..self.num_nodes as i64,
+            None => state.1..self.get_end_node(),
+        };
+        // The last interior node must also reach the end node admissibly
+        let last = self.is_terminal(&(state.0 + 1, state.1));
+        nodes
+            .filter(|&q| self.is_admissible(state.1, q))
+            .filter(|&q| !last || self.is_admissible(q, self.get_end_node()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrangian::expr::FnLagrangian;
+    use crate::lagrangian::one_dim::{FreeBody, RelativisticParticle};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn periodic_axis_wraps_around() {
+        // The short way from node 1 to node 6 of 8 angles goes backwards through node 0
+        let mut env = Lattice1D::new(8, 1, 6, 3, FreeBody::new(1f64));
+        env.set_axis(Axis::angular(8));
+        let path = env.brute_force().unwrap();
+        assert_eq!(path, vec![1, 0, 7, 6]);
+
+        let q = env.positions(&path);
+        let spacing = env.get_axis().spacing();
+        assert!(q.windows(2).all(|w| (w[1] - w[0] + spacing).abs() < 1e-12));
+    }
+
+    #[test]
+    fn periodic_axis_winds() {
+        // L = -q̇ rewards unwrapped progress, so the path winds once with steps of 2π/3
+        let lagrangian = FnLagrangian::new(|_t: f64, _q: &f64, dq: &f64| -dq);
+        let mut env = Lattice1D::new(6, 0, 0, 3, lagrangian);
+        env.set_axis(Axis::angular(6));
+        let path = env.brute_force().unwrap();
+        assert_eq!(path, vec![0, 2, 4, 0]);
+        assert!((env.action(&path) + 2f64 * PI).abs() < 1e-12);
+        assert_eq!(env.available_actions(&(0, 0)).len(), 6);
+    }
+
+    #[test]
+    fn linear_axis_stays_monotone() {
+        let env = Lattice1D::new(6, 0, 5, 3, FreeBody::new(1f64));
+        let path = env.brute_force().unwrap();
+        assert!(path.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn no_admissible_path() {
+        // Reaching node 10 in two steps needs v = 5 > c
+        let env = Lattice1D::new(11, 0, 10, 2, RelativisticParticle::new(1f64, 1f64));
+        assert_eq!(env.brute_force(), None);
+
+        // Four steps of at most two nodes each
+        let env = Lattice1D::new(11, 0, 10, 5, RelativisticParticle::new(1f64, 2.5));
+        let path = env.brute_force().unwrap();
+        assert!(path.windows(2).all(|w| w[1] - w[0] <= 2));
+    }
+
+    #[test]
+    fn last_step_reaches_end_admissibly() {
+        let env = Lattice1D::new(11, 0, 10, 3, RelativisticParticle::new(1f64, 4.5));
+        // Steps are at most 4 nodes, so from node 3 the last interior node is 6 or 7
+        assert_eq!(env.available_actions(&(2, 3)), vec![6, 7]);
+        assert_eq!(env.available_actions(&(1, 3)), (3..8).collect::<Vec<_>>());
     }
 }
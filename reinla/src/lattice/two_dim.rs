@@ -1,15 +1,15 @@
 use crate::lagrangian::Lagrangian;
-//use forger::env::Env;
+use crate::lattice::n_dim::{LatticeND, Node};
+use crate::verify::Verdict;
 
-//type S = (usize, (i64, i64));
 type Q = (f64, f64);
 
-#[allow(dead_code)]
+/// Two degrees of freedom on `LatticeND`
+///
+/// Interior nodes range over the box spanned by both end nodes unless `set_bounds` widens it
+#[derive(Debug)]
 pub struct Lattice2D<L: Lagrangian> {
-    init_node: (i64, i64),
-    end_node: (i64, i64),
-    t: usize,
-    lagrangian: L,
+    lattice: LatticeND<L>,
     ground_state: Q,
 }
 
@@ -21,25 +21,81 @@ impl<L: Lagrangian<Q = Q>> Lattice2D<L> {
         lagrangian: L,
         ground_state: (f64, f64),
     ) -> Self {
+        let bounds = vec![
+            (init_node.0.min(end_node.0), init_node.0.max(end_node.0)),
+            (init_node.1.min(end_node.1), init_node.1.max(end_node.1)),
+        ];
         Self {
-            init_node,
-            end_node,
-            t,
-            lagrangian,
+            lattice: LatticeND::new(node(init_node), node(end_node), bounds, t, lagrangian),
             ground_state,
         }
     }
 
+    /// Underlying N-D lattice with two axes
+    pub fn get_lattice(&self) -> &LatticeND<L> {
+        &self.lattice
+    }
+
+    pub fn get_lattice_mut(&mut self) -> &mut LatticeND<L> {
+        &mut self.lattice
+    }
+
+    pub fn set_bounds(&mut self, bounds: [(i64, i64); 2]) {
+        self.lattice.set_bounds(bounds.to_vec());
+    }
+
+    /// Physical duration of one time step
+    pub fn set_dt(&mut self, dt: f64) {
+        self.lattice.set_dt(dt);
+    }
+
+    pub fn get_dt(&self) -> f64 {
+        self.lattice.get_dt()
+    }
+
+    /// Physical time at the midpoint of the k-th step
+    pub fn t_mid(&self, k: usize) -> f64 {
+        self.lattice.t_mid(k)
+    }
+
     pub fn lagrangian(&self) -> &L {
-        &self.lagrangian
+        self.lattice.lagrangian()
     }
 
     pub fn l_min(&self) -> f64 {
-        self.lagrangian.calc(&self.ground_state, &(1f64, 0f64))
+        self.lagrangian().calc(&self.ground_state, &(1f64, 0f64))
     }
 
     pub fn l_amplify(&self, q: &Q, dq: &Q) -> f64 {
-        let l = self.lagrangian.calc(q, dq);
+        let l = self.lagrangian().calc(q, dq);
         (l - self.l_min()).powi(2)
     }
+
+    /// Physical coordinates of a node path, unwrapped along periodic axes
+    pub fn positions(&self, path: &[(i64, i64)]) -> Vec<Q> {
+        self.lattice.positions(&nodes(path))
+    }
+
+    /// Action of a full node path (both ends included)
+    pub fn action(&self, path: &[(i64, i64)]) -> f64 {
+        self.lattice.action(&nodes(path))
+    }
+
+    /// Path with the smallest objective, or `None` if every path leaves the domain of L
+    pub fn brute_force(&self) -> Option<Vec<(i64, i64)>> {
+        let path = self.lattice.brute_force()?;
+        Some(path.into_iter().map(|node| (node[0], node[1])).collect())
+    }
+
+    pub fn verify(&self, path: &[(i64, i64)], residual_tol: f64) -> Verdict {
+        self.lattice.verify(&nodes(path), residual_tol)
+    }
+}
+
+fn node(x: (i64, i64)) -> Node {
+    vec![x.0, x.1]
+}
+
+fn nodes(path: &[(i64, i64)]) -> Vec<Node> {
+    path.iter().map(|&x| node(x)).collect()
 }
@@ -1,4 +1,12 @@
+pub mod coordinate;
+pub mod discrete;
+pub mod hamiltonian;
+pub mod integrator;
 pub mod lagrangian;
 pub mod lattice;
+pub mod potential;
+pub mod shooting;
+pub mod stationary;
 pub mod time_lattice;
 pub mod util;
+pub mod verify;
@@ -0,0 +1,137 @@
+use peroxide::fuga::*;
+
+/// One dimensional potential energy V(q)
+pub trait Potential {
+    fn eval(&self, q: f64) -> f64;
+
+    /// Same as `eval` on dual numbers; `None` falls back to finite differences
+    fn eval_ad(&self, _q: AD) -> Option<AD> {
+        None
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Quartic Anharmonic Oscillator: V = k q² / 2 + λ q⁴
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Copy, Clone)]
+pub struct Anharmonic {
+    k: f64,
+    lambda: f64,
+}
+
+impl Anharmonic {
+    pub fn new(k: f64, lambda: f64) -> Self {
+        Self { k, lambda }
+    }
+}
+
+impl Potential for Anharmonic {
+    fn eval(&self, q: f64) -> f64 {
+        0.5 * self.k * q.powi(2) + self.lambda * q.powi(4)
+    }
+
+    fn eval_ad(&self, q: AD) -> Option<AD> {
+        Some(0.5 * self.k * q.powi(2) + self.lambda * q.powi(4))
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Duffing: V = α q² / 2 + β q⁴ / 4 (α < 0 gives two wells)
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Copy, Clone)]
+pub struct Duffing {
+    alpha: f64,
+    beta: f64,
+}
+
+impl Duffing {
+    pub fn new(alpha: f64, beta: f64) -> Self {
+        Self { alpha, beta }
+    }
+}
+
+impl Potential for Duffing {
+    fn eval(&self, q: f64) -> f64 {
+        0.5 * self.alpha * q.powi(2) + 0.25 * self.beta * q.powi(4)
+    }
+
+    fn eval_ad(&self, q: AD) -> Option<AD> {
+        Some(0.5 * self.alpha * q.powi(2) + 0.25 * self.beta * q.powi(4))
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Morse: V = D (1 - exp(-a (q - r0)))²
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Copy, Clone)]
+pub struct Morse {
+    depth: f64,
+    a: f64,
+    r0: f64,
+}
+
+impl Morse {
+    pub fn new(depth: f64, a: f64, r0: f64) -> Self {
+        Self { depth, a, r0 }
+    }
+}
+
+impl Potential for Morse {
+    fn eval(&self, q: f64) -> f64 {
+        self.depth * (1f64 - (-self.a * (q - self.r0)).exp()).powi(2)
+    }
+
+    fn eval_ad(&self, q: AD) -> Option<AD> {
+        Some(self.depth * (1f64 - (-self.a * (q - self.r0)).exp()).powi(2))
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Double Well: V = λ (q² - a²)²
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Copy, Clone)]
+pub struct DoubleWell {
+    lambda: f64,
+    a: f64,
+}
+
+impl DoubleWell {
+    pub fn new(lambda: f64, a: f64) -> Self {
+        Self { lambda, a }
+    }
+}
+
+impl Potential for DoubleWell {
+    fn eval(&self, q: f64) -> f64 {
+        self.lambda * (q.powi(2) - self.a.powi(2)).powi(2)
+    }
+
+    fn eval_ad(&self, q: AD) -> Option<AD> {
+        Some(self.lambda * (q.powi(2) - self.a.powi(2)).powi(2))
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Pöschl-Teller: V = -V0 / cosh²(a q)
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Copy, Clone)]
+pub struct PoschlTeller {
+    v0: f64,
+    a: f64,
+}
+
+impl PoschlTeller {
+    pub fn new(v0: f64, a: f64) -> Self {
+        Self { v0, a }
+    }
+}
+
+impl Potential for PoschlTeller {
+    fn eval(&self, q: f64) -> f64 {
+        -self.v0 / (self.a * q).cosh().powi(2)
+    }
+
+    fn eval_ad(&self, q: AD) -> Option<AD> {
+        Some(-self.v0 / (self.a * q).cosh().powi(2))
+    }
+}
@@ -0,0 +1,213 @@
+use crate::coordinate::Coordinate;
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+
+/// Sampled continuum trajectory
+#[derive(Debug, Clone)]
+pub struct Trajectory<Q> {
+    pub t: Vec<f64>,
+    pub q: Vec<Q>,
+    pub v0: Q,
+}
+
+/// Shooting method for q(0) = start, q(T) = end
+///
+/// Integrates the Euler-Lagrange equation with RK4 and adjusts the initial velocity by Newton iteration
+pub struct Shooting<'a, L: Lagrangian> {
+    lagrangian: &'a L,
+    t: f64,
+    substeps: usize,
+    tol: f64,
+    max_iter: usize,
+}
+
+impl<'a, L: Lagrangian> Shooting<'a, L> {
+    pub fn new(lagrangian: &'a L, t: f64) -> Self {
+        Self {
+            lagrangian,
+            t,
+            substeps: 100,
+            tol: 1e-10,
+            max_iter: 100,
+        }
+    }
+
+    /// Number of RK4 steps between two consecutive sample points
+    pub fn set_substeps(&mut self, substeps: usize) {
+        self.substeps = substeps;
+    }
+
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+    /// (q̇, q̈) from M q̈ = ∂L/∂q - (∂²L/∂q̇∂q) q̇ - ∂²L/∂q̇∂t with M = ∂²L/∂q̇²
+    fn acceleration(&self, t: f64, y: &[f64]) -> Vec<f64> {
+        let n = y.len() / 2;
+        let q = L::Q::from_slice(&y[..n]);
+        let dq = L::Q::from_slice(&y[n..]);
+        let h = self.lagrangian.hessian(t, &q, &dq);
+        let l_q = self.lagrangian.dl_dq(t, &q, &dq).to_vec();
+
+        let h_t = 1e-6 * t.abs().max(1f64);
+        let p_p = self.lagrangian.dl_ddq(t + h_t, &q, &dq).to_vec();
+        let p_m = self.lagrangian.dl_ddq(t - h_t, &q, &dq).to_vec();
+        let dp_dt = p_p.sub_v(&p_m).div_s(2f64 * h_t);
+
+        let mut mass = zeros(n, n);
+        let mut force = l_q.sub_v(&dp_dt);
+        for i in 0..n {
+            for j in 0..n {
+                mass[(i, j)] = h[(n + i, n + j)];
+                force[i] -= h[(n + i, j)] * y[n + j];
+            }
+        }
+
+        let mut deriv = y[n..].to_vec();
+        deriv.extend(mass.solve(&force, SolveKind::LU));
+        deriv
+    }
+
+    /// Integrate from (q0, v0) and return the state at every RK4 step
+    ///
+    /// Classic RK4 is stepped here since `ExplicitODE` of peroxide 0.34 offsets the fourth stage by h/2 k1
+    fn integrate(&self, q0: &[f64], v0: &[f64], n_nodes: usize) -> Vec<Vec<f64>> {
+        let steps = (n_nodes + 1) * self.substeps;
+        let h = self.t / steps as f64;
+        let mut y = q0.to_vec();
+        y.extend_from_slice(v0);
+
+        let mut result = vec![y.clone()];
+        for step in 0..steps {
+            let t = step as f64 * h;
+            let k1 = self.acceleration(t, &y);
+            let k2 = self.acceleration(t + 0.5 * h, &y.add_v(&k1.mul_s(0.5 * h)));
+            let k3 = self.acceleration(t + 0.5 * h, &y.add_v(&k2.mul_s(0.5 * h)));
+            let k4 = self.acceleration(t + h, &y.add_v(&k3.mul_s(h)));
+            for i in 0..y.len() {
+                y[i] += h / 6f64 * (k1[i] + 2f64 * k2[i] + 2f64 * k3[i] + k4[i]);
+            }
+            result.push(y.clone());
+        }
+        result
+    }
+
+    fn end_point(&self, q0: &[f64], v0: &[f64], n_nodes: usize) -> Vec<f64> {
+        let result = self.integrate(q0, v0, n_nodes);
+        result[result.len() - 1][..q0.len()].to_vec()
+    }
+
+    /// Solve the boundary value problem and sample it on t_k = k T / (n_nodes + 1)
+    ///
+    /// Returns `None` if the shooting iteration does not converge
+    pub fn solve(&self, start: &L::Q, end: &L::Q, n_nodes: usize) -> Option<Trajectory<L::Q>> {
+        let q0 = start.to_vec();
+        let q1 = end.to_vec();
+        let n = q0.len();
+
+        let mut v0 = q1.sub_v(&q0).div_s(self.t);
+        let mut converged = false;
+        for _ in 0..self.max_iter {
+            let f = self.end_point(&q0, &v0, n_nodes).sub_v(&q1);
+            let scale = q1.iter().fold(1f64, |acc, x| acc.max(x.abs()));
+            if f.iter().all(|x| x.abs() <= self.tol * scale) {
+                converged = true;
+                break;
+            }
+
+            // Finite difference Jacobian ∂q(T)/∂v0
+            let mut jacobian = zeros(n, n);
+            for j in 0..n {
+                let h = 1e-6 * v0[j].abs().max(1f64);
+                let mut v_h = v0.clone();
+                v_h[j] += h;
+                let f_h = self.end_point(&q0, &v_h, n_nodes).sub_v(&q1);
+                for i in 0..n {
+                    jacobian[(i, j)] = (f_h[i] - f[i]) / h;
+                }
+            }
+            v0 = v0.sub_v(&jacobian.solve(&f, SolveKind::LU));
+        }
+
+        if !converged {
+            return None;
+        }
+
+        let result = self.integrate(&q0, &v0, n_nodes);
+        let t = linspace(0, self.t, n_nodes + 2);
+        let q = (0..=n_nodes + 1)
+            .map(|k| L::Q::from_slice(&result[k * self.substeps][..n]))
+            .collect();
+
+        Some(Trajectory {
+            t,
+            q,
+            v0: L::Q::from_slice(&v0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrangian::one_dim::{CaldirolaKanai, UniformGravity, SHO};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn sho_matches_analytic() {
+        // q(t) = B sin(t) / sin(T) for q(0) = 0, q(T) = B
+        let (b, t_end) = (20f64, 2f64 * PI / 3f64);
+        let sho = SHO::new(1f64, 1f64);
+        let trajectory = Shooting::new(&sho, t_end).solve(&0f64, &b, 7).unwrap();
+        assert_eq!(trajectory.q.len(), 9);
+        assert!((trajectory.v0 - b / t_end.sin()).abs() < 1e-8);
+        for (t, q) in trajectory.t.iter().zip(&trajectory.q) {
+            assert!((q - b * t.sin() / t_end.sin()).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn free_fall_matches_analytic() {
+        // q(t) = v0 t - g t² / 2 with v0 = B / T + g T / 2
+        let (g, b, t_end) = (2f64, 1f64, 1.5);
+        let gravity = UniformGravity::new(1f64, g);
+        let trajectory = Shooting::new(&gravity, t_end).solve(&0f64, &b, 5).unwrap();
+        let v0 = b / t_end + 0.5 * g * t_end;
+        assert!((trajectory.v0 - v0).abs() < 1e-10);
+        for (t, q) in trajectory.t.iter().zip(&trajectory.q) {
+            assert!((q - (v0 * t - 0.5 * g * t.powi(2))).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn sho_at_half_period_has_no_solution() {
+        // q(π) = -q(0) for every initial velocity, so q(π) = 20 cannot be reached
+        let sho = SHO::new(1f64, 1f64);
+        let mut shooting = Shooting::new(&sho, PI);
+        shooting.set_max_iter(10);
+        assert!(shooting.solve(&0f64, &20f64, 7).is_none());
+    }
+
+    #[test]
+    fn rk4_matches_damped_oscillator() {
+        // Caldirola-Kanai gives q̈ + γ q̇ + ω² q = 0, so q(t) = e^(-γt/2) cos(Ω t)
+        // with Ω² = ω² - γ²/4 for q(0) = 1, q̇(0) = -γ/2; this needs the ∂²L/∂q̇∂t term
+        let gamma = 0.5;
+        let omega = (1f64 - gamma.powi(2) / 4f64).sqrt();
+        let ck = CaldirolaKanai::new(1f64, 1f64, gamma);
+        let shooting = Shooting::new(&ck, 3f64);
+        let result = shooting.integrate(&[1f64], &[-0.5 * gamma], 2);
+        assert_eq!(result.len(), 3 * 100 + 1);
+
+        let h = 3f64 / 300f64;
+        for (step, y) in result.iter().enumerate() {
+            let t = step as f64 * h;
+            let q = (-0.5 * gamma * t).exp() * (omega * t).cos();
+            assert!((y[0] - q).abs() < 1e-8, "t = {}: {} vs {}", t, y[0], q);
+        }
+    }
+}
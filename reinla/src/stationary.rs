@@ -0,0 +1,135 @@
+use crate::coordinate::Coordinate;
+use crate::discrete::DiscreteLagrangian;
+use crate::lagrangian::Lagrangian;
+use peroxide::fuga::*;
+
+/// Path with vanishing discrete Euler-Lagrange residual and its second variation
+#[derive(Debug, Clone)]
+pub struct StationaryPath<Q> {
+    pub path: Vec<Q>,
+    pub action: f64,
+    pub max_residual: f64,
+    /// Eigenvalues of the action Hessian w.r.t. interior nodes, ascending
+    pub eigenvalues: Vec<f64>,
+    /// Number of negative eigenvalues; 0 for a minimum, > 0 for a saddle
+    pub morse_index: usize,
+    pub iterations: usize,
+}
+
+/// Newton iteration on the discrete Euler-Lagrange equations with fixed end points
+///
+/// Unlike minimizing the action, this also finds saddle points (e.g. SHO with ωT > π)
+pub struct StationarySolver<'a, L: Lagrangian> {
+    discrete: DiscreteLagrangian<'a, L>,
+    tol: f64,
+    max_iter: usize,
+}
+
+impl<'a, L: Lagrangian> StationarySolver<'a, L> {
+    pub fn new(lagrangian: &'a L, dt: f64) -> Self {
+        Self {
+            discrete: DiscreteLagrangian::new(lagrangian, dt),
+            tol: 1e-10,
+            max_iter: 50,
+        }
+    }
+
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+    /// Sum of squared discrete Euler-Lagrange residuals over interior nodes
+    pub fn residual_sq(&self, path: &[L::Q]) -> f64 {
+        self.discrete
+            .el_residual(path)
+            .iter()
+            .flatten()
+            .map(|r| r.powi(2))
+            .sum()
+    }
+
+    /// Hessian of the discrete action w.r.t. the interior nodes (block tridiagonal)
+    pub fn hessian(&self, path: &[L::Q]) -> Matrix {
+        let n = path[0].dim();
+        let m = path.len() - 2;
+        let mut h = zeros(n * m, n * m);
+        for (k, w) in path.windows(2).enumerate() {
+            let (d11, d12, d22) = self.discrete.hessian(self.discrete.t_mid(k), &w[0], &w[1]);
+            // Segment k couples interior nodes k - 1 and k (ends are fixed)
+            for i in 0..n {
+                for j in 0..n {
+                    if k >= 1 {
+                        h[((k - 1) * n + i, (k - 1) * n + j)] += d11[(i, j)];
+                    }
+                    if k < m {
+                        h[(k * n + i, k * n + j)] += d22[(i, j)];
+                    }
+                    if k >= 1 && k < m {
+                        h[((k - 1) * n + i, k * n + j)] += d12[(i, j)];
+                        h[(k * n + i, (k - 1) * n + j)] += d12[(j, i)];
+                    }
+                }
+            }
+        }
+        h
+    }
+
+    /// Action, residual and Morse index of a path without iterating
+    pub fn analyze(&self, path: Vec<L::Q>, iterations: usize) -> StationaryPath<L::Q> {
+        let residual = self.discrete.el_residual(&path);
+        let max_residual = residual
+            .iter()
+            .flatten()
+            .fold(0f64, |acc, r| acc.max(r.abs()));
+        let mut eigenvalues = if path.len() > 2 {
+            eigen(&self.hessian(&path), EigenMethod::Jacobi).eigenvalue
+        } else {
+            vec![]
+        };
+        eigenvalues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let morse_index = eigenvalues.iter().filter(|&&e| e < 0f64).count();
+
+        StationaryPath {
+            action: self.discrete.action(&path),
+            path,
+            max_residual,
+            eigenvalues,
+            morse_index,
+            iterations,
+        }
+    }
+
+    /// Newton iteration from `guess` (both ends included and kept fixed)
+    ///
+    /// Returns `None` if the residual does not drop below `tol`
+    pub fn solve(&self, guess: Vec<L::Q>) -> Option<StationaryPath<L::Q>> {
+        let n = guess[0].dim();
+        let mut path = guess;
+        for iter in 0..=self.max_iter {
+            let residual = self
+                .discrete
+                .el_residual(&path)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<f64>>();
+            if residual.iter().all(|r| r.abs() <= self.tol) {
+                return Some(self.analyze(path, iter));
+            }
+            if iter == self.max_iter {
+                break;
+            }
+
+            let delta = self.hessian(&path).solve(&residual, SolveKind::LU);
+            let last = path.len() - 1;
+            for (k, q) in path[1..last].iter_mut().enumerate() {
+                let x = q.to_vec().sub_v(&delta[k * n..(k + 1) * n].to_vec());
+                *q = L::Q::from_slice(&x);
+            }
+        }
+        None
+    }
+}
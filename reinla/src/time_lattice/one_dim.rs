@@ -61,9 +61,10 @@ impl<L: Lagrangian<Q = f64>, const T: usize> TimeLattice1D<L, T> {
         self.t_vec.len()
     }
 
+    /// Lagrangian of the segment whose midpoint is at time t
     #[allow(non_snake_case)]
-    pub fn L(&self, q: f64, dq: f64) -> f64 {
-        self.lagrangian.calc(&q, &dq)
+    pub fn L(&self, t: f64, q: f64, dq: f64) -> f64 {
+        self.lagrangian.calc_t(t, &q, &dq)
     }
 
     pub fn set_s_min_max(&mut self, s_min: f64, s_max: f64) {
@@ -99,10 +100,11 @@ impl<L: Lagrangian<Q = f64>, const T: usize> Env<S<T>, A> for TimeLattice1D<L, T
         let s = state_vec[0..T + 1]
             .iter()
             .zip(state_vec[1..T + 2].iter())
-            .fold(0f64, |acc, (q_c, q_n)| {
+            .enumerate()
+            .fold(0f64, |acc, (k, (q_c, q_n))| {
                 let q = (*q_c as f64 + *q_n as f64) / 2.0;
                 let dq = *q_n as f64 - *q_c as f64;
-                acc + self.L(q, dq)
+                acc + self.L(k as f64 + 0.5, q, dq)
             });
 
         let mut state_new = [0usize; T];
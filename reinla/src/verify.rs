@@ -0,0 +1,129 @@
+use crate::coordinate::Coordinate;
+use crate::discrete::DiscreteLagrangian;
+use crate::lagrangian::Lagrangian;
+
+/// Result of checking a discrete path against the equations of motion
+#[derive(Debug, Clone)]
+pub struct Verdict {
+    pub residuals: Vec<Vec<f64>>,
+    pub max_residual: f64,
+    pub max_deviation: Option<f64>,
+    /// Tolerance on the residual, in units of force × dt
+    pub residual_tol: f64,
+    /// Tolerance on the deviation, in units of q
+    pub deviation_tol: Option<f64>,
+    pub passed: bool,
+}
+
+fn max_abs(v: &[f64]) -> f64 {
+    v.iter().fold(0f64, |acc, x| acc.max(x.abs()))
+}
+
+/// Discrete Euler-Lagrange residual at every interior node of `path`
+pub fn el_residual<L: Lagrangian>(lagrangian: &L, path: &[L::Q], dt: f64) -> Vec<Vec<f64>> {
+    DiscreteLagrangian::new(lagrangian, dt).el_residual(path)
+}
+
+/// |q_k - q(t_k)| at every node, with t_k = k * dt
+pub fn deviation<Q: Coordinate, F: Fn(f64) -> Q>(path: &[Q], dt: f64, exact: F) -> Vec<f64> {
+    path.iter()
+        .enumerate()
+        .map(|(k, q)| {
+            let q_true = exact(k as f64 * dt).to_vec();
+            let d = q
+                .to_vec()
+                .iter()
+                .zip(q_true)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>();
+            d.sqrt()
+        })
+        .collect()
+}
+
+pub fn verify<L: Lagrangian>(lagrangian: &L, path: &[L::Q], dt: f64, residual_tol: f64) -> Verdict {
+    let residuals = el_residual(lagrangian, path, dt);
+    let max_residual = residuals.iter().fold(0f64, |acc, r| acc.max(max_abs(r)));
+
+    Verdict {
+        residuals,
+        max_residual,
+        max_deviation: None,
+        residual_tol,
+        deviation_tol: None,
+        passed: max_residual <= residual_tol,
+    }
+}
+
+/// Same as `verify`, also requiring the path to stay within `deviation_tol` of the continuum
+/// solution
+pub fn verify_with_solution<L: Lagrangian, F: Fn(f64) -> L::Q>(
+    lagrangian: &L,
+    path: &[L::Q],
+    dt: f64,
+    residual_tol: f64,
+    deviation_tol: f64,
+    exact: F,
+) -> Verdict {
+    let mut verdict = verify(lagrangian, path, dt, residual_tol);
+    let max_deviation = max_abs(&deviation(path, dt, exact));
+    verdict.max_deviation = Some(max_deviation);
+    verdict.deviation_tol = Some(deviation_tol);
+    verdict.passed &= max_deviation <= deviation_tol;
+    verdict
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrangian::one_dim::{FreeBody, UniformGravity};
+
+    const DT: f64 = 0.1;
+
+    /// q(t) = v0 t - g t² / 2, which the midpoint rule reproduces exactly
+    fn parabola(t: f64) -> f64 {
+        3f64 * t - 0.5 * 2f64 * t.powi(2)
+    }
+
+    fn sampled(n: usize) -> Vec<f64> {
+        (0..=n).map(|k| parabola(k as f64 * DT)).collect()
+    }
+
+    #[test]
+    fn exact_discrete_path_passes() {
+        let line = (0..=10).map(|k| 0.5 * k as f64).collect::<Vec<_>>();
+        let verdict = verify(&FreeBody::new(1f64), &line, DT, 1e-10);
+        assert!(verdict.passed);
+        assert_eq!(verdict.residuals.len(), 9);
+
+        let path = sampled(10);
+        let verdict = verify_with_solution(
+            &UniformGravity::new(1f64, 2f64),
+            &path,
+            DT,
+            1e-10,
+            1e-10,
+            parabola,
+        );
+        assert!(verdict.passed, "{verdict:?}");
+        assert_eq!(verdict.deviation_tol, Some(1e-10));
+    }
+
+    #[test]
+    fn perturbed_path_fails() {
+        let lagrangian = UniformGravity::new(1f64, 2f64);
+        let mut path = sampled(10);
+        path[4] += 0.01;
+
+        let verdict = verify(&lagrangian, &path, DT, 1e-3);
+        assert!(!verdict.passed);
+        // Only the node itself and its neighbours feel the kink
+        assert!(verdict.residuals[3][0].abs() > 1e-3);
+        assert!(verdict.residuals[0][0].abs() < 1e-10);
+
+        // The residual alone passes at a loose tolerance, the deviation does not
+        let verdict = verify_with_solution(&lagrangian, &path, DT, 1f64, 1e-3, parabola);
+        assert!(verdict.max_residual <= 1f64);
+        assert!(!verdict.passed);
+    }
+}